@@ -0,0 +1,79 @@
+use crate::board::{Board, BOARD_LEN, FULL_MASK};
+use crate::trie::Trie;
+
+/// What can be told about a board from its words alone, before searching for a solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    /// number of words that can be played on the board
+    pub legal_words: usize,
+    /// letters no word uses, the board has no solution if there are any
+    pub unused: Vec<char>,
+    /// letters no word starts with, so they can't join two words
+    pub never_start: Vec<char>,
+    /// letters no word ends with, so they can't join two words
+    pub never_end: Vec<char>,
+    /// letters some words end with but no word starts with: those words can only end a solution
+    pub dead_ends: Vec<char>,
+    /// Fewest words any solution needs, given that no word covers more letters than the best
+    /// one. `None` if the board has no solution.
+    pub min_words: Option<usize>,
+}
+
+impl Analysis {
+    /// whether the board certainly has no solution
+    pub fn impossible(&self) -> bool {
+        self.min_words.is_none()
+    }
+}
+
+/// Check which letters of the board the words of `trie` can cover and chain through.
+pub fn analyze(board: &Board, trie: &Trie) -> Analysis {
+    let mut used = 0u16;
+    let mut starts = 0u16;
+    let mut ends = 0u16;
+    let mut max_cover = 0;
+    for word in trie.iter() {
+        let mask = board.get_mask(&word);
+        used |= mask;
+        starts |= 1 << board.get_idx(word.chars().next().unwrap());
+        ends |= 1 << board.get_idx(word.chars().last().unwrap());
+        max_cover = max_cover.max(mask.count_ones() as usize);
+    }
+    let letters = |mask: u16| -> Vec<char> {
+        (0..BOARD_LEN)
+            .filter(|i| mask & 1 << i != 0)
+            .map(|i| board.letters[i])
+            .collect()
+    };
+    Analysis {
+        legal_words: trie.len(),
+        unused: letters(FULL_MASK & !used),
+        never_start: letters(used & !starts),
+        never_end: letters(used & !ends),
+        dead_ends: letters(ends & !starts),
+        min_words: (used == FULL_MASK).then(|| BOARD_LEN.div_ceil(max_cover)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn analyze_board() {
+        let board = Board::from("vkspyielurao".chars());
+        let trie = Trie::new_with_board(["previously", "yak", "kayo"], &board);
+        let analysis = analyze(&board, &trie);
+        assert_eq!(analysis.legal_words, 3);
+        assert!(analysis.unused.is_empty());
+        assert_eq!(analysis.dead_ends, vec!['o']);
+        assert_eq!(analysis.never_end.len(), 12 - 3);
+        assert_eq!(analysis.min_words, Some(2));
+        assert!(!analysis.impossible());
+
+        let trie = Trie::new_with_board(["perilously", "kayo"], &board);
+        let analysis = analyze(&board, &trie);
+        assert_eq!(analysis.unused, vec!['v']);
+        assert!(analysis.impossible());
+    }
+}
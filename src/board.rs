@@ -1,5 +1,16 @@
 pub const BOARD_LEN: usize = 12;
 pub const SIDE_LEN: usize = BOARD_LEN / 4;
+/// Letter mask with every letter on the board covered
+pub const FULL_MASK: u16 = (1 << BOARD_LEN) - 1;
+
+/// A rule of the board broken by the letters of a word
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LetterError {
+    /// The letter isn't on the board.
+    NotOnBoard(char),
+    /// The two consecutive letters are on the same side of the board.
+    SameSide(char, char),
+}
 
 /// Struct to represent the letterboxed board
 #[derive(Hash, Eq, PartialEq, Debug)]
@@ -60,6 +71,34 @@ impl Board {
             .unwrap()
             .0
     }
+    /// get the mask of board letters used by a word, bit `i` is set if `letters[i]` is used.
+    pub fn get_mask(&self, word: &str) -> u16 {
+        word.chars().fold(0, |mask, c| mask | 1 << self.get_idx(c))
+    }
+    /// get the side of the board a character is on, `None` if it isn't on the board
+    pub fn get_side(&self, c: char) -> Option<usize> {
+        self.letters
+            .iter()
+            .position(|&l| l == c)
+            .map(|i| i / SIDE_LEN)
+    }
+    /// check that every letter of a word is on the board and that no two consecutive letters
+    /// are on the same side, returning every rule broken
+    pub fn check_letters(&self, word: &str) -> Vec<LetterError> {
+        let mut broken = vec![];
+        let mut prev_char: Option<char> = None;
+        for c in word.chars() {
+            match (prev_char.and_then(|p| self.get_side(p)), self.get_side(c)) {
+                (_, None) => broken.push(LetterError::NotOnBoard(c)),
+                (Some(p), Some(s)) if p == s => {
+                    broken.push(LetterError::SameSide(prev_char.unwrap(), c))
+                }
+                _ => (),
+            }
+            prev_char = Some(c);
+        }
+        broken
+    }
 }
 
 impl<T> From<T> for Board
@@ -115,6 +154,27 @@ mod test {
         assert!(std::panic::catch_unwind(|| Board::from("abcdefghijklm".chars())).is_err());
     }
 
+    #[test]
+    fn letter_mask() {
+        let b = Board::from("abcdefghijkl".chars());
+        assert_eq!(b.get_mask("adg"), 0b1001001);
+        assert_eq!(b.get_mask("lala"), 0b100000000001);
+        assert_eq!(b.get_mask("abcdefghijkl"), FULL_MASK);
+    }
+
+    #[test]
+    fn letter_errors() {
+        let b = Board::from("abcdefghijkl".chars());
+        assert_eq!(b.check_letters("adgj"), vec![]);
+        assert_eq!(
+            b.check_letters("abzd"),
+            vec![
+                LetterError::SameSide('a', 'b'),
+                LetterError::NotOnBoard('z')
+            ]
+        );
+    }
+
     #[test]
     fn board_is_square() {
         assert!((BOARD_LEN as f32 / SIDE_LEN as f32) - 4.0 <= f32::EPSILON);
@@ -0,0 +1,98 @@
+use crate::trie::Trie;
+
+/// Join words into the continuous chain of letters the game is played with, writing the letter
+/// shared by consecutive words once, eg: "SENTRYESELFUNDSWUNG" for SENTRY, YES, SELF, FUNDS,
+/// SWUNG.
+pub fn to_chain<S: AsRef<str>>(words: &[S]) -> String {
+    let mut chain = String::new();
+    for (i, word) in words.iter().enumerate() {
+        let mut letters = word.as_ref().chars();
+        if i > 0 {
+            letters.next();
+        }
+        chain.extend(letters);
+    }
+    chain
+}
+
+/// Every way to split `chain` into words of `trie`, where each word starts with the last letter
+/// of the word before it. Segmentations are ordered by their words, shorter words first.
+pub fn segment(trie: &Trie, chain: &str) -> Vec<Vec<String>> {
+    let letters: Vec<char> = chain.chars().collect();
+    let last = match letters.len() {
+        0 => return vec![],
+        n => n - 1,
+    };
+    // words[i] holds the end of every word starting at letter i
+    let words: Vec<Vec<usize>> = (0..letters.len())
+        .map(|start| {
+            (start + 1..letters.len())
+                .filter(|&end| trie.contains(&letters[start..=end].iter().collect::<String>()))
+                .collect()
+        })
+        .collect();
+    // finishes[i] is set if the rest of the chain can be split into words when a word starts at i
+    let mut finishes = vec![false; letters.len()];
+    for start in (0..letters.len()).rev() {
+        finishes[start] = words[start].iter().any(|&end| end == last || finishes[end]);
+    }
+
+    let mut segmentations = vec![];
+    let mut path = vec![];
+    if finishes[0] {
+        collect(
+            &letters,
+            &words,
+            &finishes,
+            0,
+            &mut path,
+            &mut segmentations,
+        );
+    }
+    segmentations
+}
+
+/// depth first search for [`segment`], only visiting starts that lead to a full segmentation
+fn collect(
+    letters: &[char],
+    words: &[Vec<usize>],
+    finishes: &[bool],
+    start: usize,
+    path: &mut Vec<String>,
+    segmentations: &mut Vec<Vec<String>>,
+) {
+    for &end in &words[start] {
+        path.push(letters[start..=end].iter().collect());
+        if end == letters.len() - 1 {
+            segmentations.push(path.clone());
+        } else if finishes[end] {
+            collect(letters, words, finishes, end, path, segmentations);
+        }
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn chain_round_trip() {
+        let trie = Trie::new([
+            "sentry", "yes", "self", "elf", "funds", "fund", "swung", "dsw",
+        ]);
+        let words = ["sentry", "yes", "self", "funds", "swung"];
+        let chain = to_chain(&words);
+        assert_eq!(chain, "sentryeselfundswung");
+        assert_eq!(segment(&trie, &chain), vec![words.to_vec()]);
+        assert_eq!(segment(&trie, "sentryes"), vec![vec!["sentry", "yes"]]);
+        assert!(segment(&trie, "sentryess").is_empty());
+        assert!(segment(&trie, "").is_empty());
+
+        let trie = Trie::new(["ab", "bc", "abc", "cd", "bcd"]);
+        assert_eq!(
+            segment(&trie, "abcd"),
+            vec![vec!["ab", "bc", "cd"], vec!["ab", "bcd"], vec!["abc", "cd"]]
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::board::{Board, BOARD_LEN};
+use crate::rng::Rng;
+use crate::solver::{solve_minimal, Solution, SolveOptions};
+use crate::trie::Trie;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// A board and a solution with the fewest words
+#[derive(Debug)]
+pub struct Puzzle {
+    pub board: Board,
+    /// number of words that can be played on the board
+    pub legal_words: usize,
+    pub solution: Solution,
+}
+
+/// The line of a puzzle file: the board letters, the par, the number of legal words and the
+/// solution, separated by tabs, eg: "RLOFAUIYESDN\t2\t594\tINFRARED,DELIRIOUSLY"
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}",
+            self.board.letters.iter().collect::<String>().to_uppercase(),
+            self.solution.words.len(),
+            self.legal_words,
+            self.solution.words.join(",").to_uppercase()
+        )
+    }
+}
+
+/// What [`generate`] accepts a board for
+#[derive(Debug, Clone)]
+pub struct GenerateOptions {
+    /// number of words of the shortest solution
+    pub par: usize,
+    /// accepted numbers of words that can be played on the board
+    pub legal_words: RangeInclusive<usize>,
+    /// boards tried before giving up
+    pub attempts: usize,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        GenerateOptions {
+            par: 3,
+            legal_words: 50..=1000,
+            attempts: 1000,
+        }
+    }
+}
+
+/// How often each letter from a to z appears in `words`
+pub(crate) fn letter_weights(words: &[String]) -> [usize; 26] {
+    let mut weights = [0; 26];
+    for c in words.iter().flat_map(|w| w.chars()) {
+        if c.is_ascii_lowercase() {
+            weights[(c as u8 - b'a') as usize] += 1;
+        }
+    }
+    weights
+}
+
+/// Board of distinct letters in random order, each picked with probability proportional to its
+/// weight. At least `BOARD_LEN` weights must be positive.
+fn random_board(rng: &mut Rng, weights: &[usize; 26]) -> Board {
+    let mut weights = *weights;
+    let mut letters = vec![];
+    while letters.len() < BOARD_LEN {
+        let i = rng.weighted(&weights);
+        weights[i] = 0;
+        letters.push((b'a' + i as u8) as char);
+    }
+    Board::from(letters)
+}
+
+/// Make a random board whose shortest solution has exactly `options.par` words and that has an
+/// accepted number of legal words, using the words of a dictionary. Letters are picked weighted
+/// by how often they appear in `words`, so that common letters show up more often than rare
+/// ones. Returns `None` if no board is accepted within `options.attempts` tries.
+pub fn generate(words: &[String], rng: &mut Rng, options: &GenerateOptions) -> Option<Puzzle> {
+    let weights = letter_weights(words);
+    if weights.iter().filter(|&&w| w > 0).count() < BOARD_LEN {
+        return None;
+    }
+    let solve_options = SolveOptions {
+        max_words: options.par,
+        ..Default::default()
+    };
+    for _ in 0..options.attempts {
+        let board = random_board(rng, &weights);
+        let trie = Trie::new_with_board(words, &board);
+        if !options.legal_words.contains(&trie.len()) {
+            continue;
+        }
+        match solve_minimal(&board, &trie, &solve_options) {
+            Ok(solution) if solution.words.len() == options.par => {
+                return Some(Puzzle {
+                    board,
+                    legal_words: trie.len(),
+                    solution,
+                })
+            }
+            _ => continue,
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::trie::{read_words, DEFAULT_WORDS};
+
+    #[test]
+    fn generate_with_par() {
+        let words: Vec<String> = read_words(DEFAULT_WORDS).collect();
+        let options = GenerateOptions {
+            par: 3,
+            legal_words: 100..=2000,
+            attempts: 1000,
+        };
+        let puzzle = generate(&words, &mut Rng::new(7), &options).unwrap();
+        assert_eq!(puzzle.solution.words.len(), 3);
+        assert_eq!(puzzle.solution.ruled_out, 2);
+        assert!(options.legal_words.contains(&puzzle.legal_words));
+        let mut letters = puzzle.board.letters.to_vec();
+        letters.sort();
+        letters.dedup();
+        assert_eq!(letters.len(), BOARD_LEN);
+
+        // the same seed makes the same board
+        let again = generate(&words, &mut Rng::new(7), &options).unwrap();
+        assert_eq!(again.board, puzzle.board);
+
+        assert!(generate(&words[..1], &mut Rng::new(7), &options).is_none());
+    }
+}
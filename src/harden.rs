@@ -0,0 +1,165 @@
+use crate::board::{Board, BOARD_LEN, SIDE_LEN};
+use crate::generate::Puzzle;
+use crate::rng::Rng;
+use crate::solver::{minimal_solutions, solve_minimal, Solution, SolveOptions};
+use crate::trie::Trie;
+
+/// What makes a board hard for [`harden`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    /// The shortest solution uses as many words as possible.
+    MinWords,
+    /// As few solutions as possible use the fewest words.
+    FewestSolutions,
+    /// As few words as possible can be played on the board.
+    FewestWords,
+}
+
+/// How [`harden`] searches
+#[derive(Debug, Clone)]
+pub struct HardenOptions {
+    pub difficulty: Difficulty,
+    /// number of letter swaps tried
+    pub iterations: usize,
+    /// Starting temperature of the annealing, cooled linearly to 0. A swap making the board
+    /// easier by `d` is kept with probability `exp(-d / temperature)`, 0 only keeps swaps that
+    /// don't make the board easier.
+    pub temperature: f64,
+    /// most words of an accepted solution, boards needing more are treated as unsolvable
+    pub max_words: usize,
+    /// solutions counted at most for [`Difficulty::FewestSolutions`], at least one is always
+    /// counted
+    pub max_solutions: usize,
+}
+
+impl Default for HardenOptions {
+    fn default() -> Self {
+        HardenOptions {
+            difficulty: Difficulty::MinWords,
+            iterations: 200,
+            temperature: 1.0,
+            max_words: 6,
+            max_solutions: 100,
+        }
+    }
+}
+
+/// The puzzle of a board and how hard it is, higher is harder. `None` if the board has no
+/// solution within `options.max_words` words.
+fn evaluate(words: &[String], board: Board, options: &HardenOptions) -> Option<(f64, Puzzle)> {
+    let trie = Trie::new_with_board(words, &board);
+    let solve_options = SolveOptions {
+        max_words: options.max_words,
+        ..Default::default()
+    };
+    let (score, solution) = match options.difficulty {
+        Difficulty::FewestSolutions => {
+            let limit = Some(options.max_solutions.max(1));
+            let found = minimal_solutions(&board, &trie, &solve_options, limit).ok()?;
+            let score = -(found.solutions.len() as f64);
+            let solution = Solution {
+                words: found.solutions.into_iter().next().unwrap(),
+                ruled_out: found.par - 1,
+            };
+            (score, solution)
+        }
+        Difficulty::MinWords => {
+            let solution = solve_minimal(&board, &trie, &solve_options).ok()?;
+            (solution.words.len() as f64, solution)
+        }
+        Difficulty::FewestWords => (
+            -(trie.len() as f64),
+            solve_minimal(&board, &trie, &solve_options).ok()?,
+        ),
+    };
+    Some((
+        score,
+        Puzzle {
+            board,
+            legal_words: trie.len(),
+            solution,
+        },
+    ))
+}
+
+/// Swap two letters on different sides
+fn swap_letters(rng: &mut Rng, letters: &mut [char; BOARD_LEN]) {
+    let i = rng.below(BOARD_LEN);
+    // skip over the side of `i`
+    let mut j = rng.below(BOARD_LEN - SIDE_LEN);
+    if j >= i / SIDE_LEN * SIDE_LEN {
+        j += SIDE_LEN;
+    }
+    letters.swap(i, j);
+}
+
+/// Rearrange the letters of `start` across its sides to make the board as hard as possible while
+/// keeping it solvable, using simulated annealing over swaps of letters on different sides.
+/// Returns the hardest board seen with its score, higher is harder: the number of words of the
+/// shortest solution, or minus the number of solutions or legal words. `None` if `start` has no
+/// solution within `options.max_words` words.
+pub fn harden(
+    words: &[String],
+    start: &Board,
+    rng: &mut Rng,
+    options: &HardenOptions,
+) -> Option<(f64, Puzzle)> {
+    let mut letters = start.letters;
+    let mut best = evaluate(words, Board::from(letters), options)?;
+    let mut current = best.0;
+    for step in 0..options.iterations {
+        let temperature = options.temperature * (1.0 - step as f64 / options.iterations as f64);
+        let mut candidate = letters;
+        swap_letters(rng, &mut candidate);
+        let Some((score, puzzle)) = evaluate(words, Board::from(candidate), options) else {
+            continue;
+        };
+        let delta = score - current;
+        if delta >= 0.0 || (temperature > 0.0 && rng.unit() < (delta / temperature).exp()) {
+            letters = candidate;
+            current = score;
+            if score > best.0 {
+                best = (score, puzzle);
+            }
+        }
+    }
+    Some(best)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::trie::{read_words, DEFAULT_WORDS};
+
+    #[test]
+    fn harden_board() {
+        let words: Vec<String> = read_words(DEFAULT_WORDS).collect();
+        let start = Board::from("vkspyielurao".chars());
+        let options = HardenOptions {
+            difficulty: Difficulty::FewestWords,
+            iterations: 30,
+            ..Default::default()
+        };
+        let (score, puzzle) = harden(&words, &start, &mut Rng::new(3), &options).unwrap();
+        let start_words = Trie::new_with_board(&words, &start).len();
+        assert!(puzzle.legal_words <= start_words);
+        assert_eq!(score, -(puzzle.legal_words as f64));
+        let mut letters = puzzle.board.letters;
+        letters.sort();
+        let mut start_letters = start.letters;
+        start_letters.sort();
+        assert_eq!(letters, start_letters);
+
+        let mut letters = start.letters;
+        let mut rng = Rng::new(1);
+        for _ in 0..100 {
+            let before = letters;
+            swap_letters(&mut rng, &mut letters);
+            let moved: Vec<usize> = (0..BOARD_LEN)
+                .filter(|&i| letters[i] != before[i])
+                .collect();
+            assert_eq!(moved.len(), 2);
+            assert_ne!(moved[0] / SIDE_LEN, moved[1] / SIDE_LEN);
+        }
+    }
+}
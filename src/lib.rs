@@ -0,0 +1,11 @@
+pub mod analysis;
+pub mod board;
+pub mod chain;
+pub mod generate;
+pub mod harden;
+mod non_nan;
+pub mod rate;
+pub mod rng;
+pub mod solver;
+pub mod trie;
+pub mod validate;
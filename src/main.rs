@@ -1,69 +1,940 @@
-mod board;
-mod non_nan;
-mod solver;
-mod trie;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use letter_box::analysis::analyze;
+use letter_box::board::{Board, BOARD_LEN, FULL_MASK};
+use letter_box::chain::{segment, to_chain};
+use letter_box::generate::{generate, GenerateOptions};
+use letter_box::harden::{harden, Difficulty, HardenOptions};
+use letter_box::rate::{rate, RateOptions};
+use letter_box::rng::Rng;
+use letter_box::solver::{
+    best_partial, clues, hint, junctions, minimal_solutions, pareto_front, solve_all,
+    solve_anytime, solve_minimal, solve_top_k, AStar, Beam, BreadthFirst, Coverage, Diversity,
+    FewestLetters, FewestRepeats, FewestWords, Greedy, Lexicographic, MinimalAStar, Parallel,
+    Progress, ProgressCallback, Scorer, SearchStats, SolveError, SolveOptions, Solver,
+    MAX_REQUIRED_WORDS,
+};
+use letter_box::trie::{read_words, Trie, DEFAULT_WORDS};
+use letter_box::validate::validate;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use board::Board;
-use clap::Parser;
-use solver::solve;
-use trie::Trie;
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Strategy {
+    /// Best-first A* search, quick but may not use the fewest words.
+    Astar,
+    /// A* search with an admissible bound, always uses the fewest words.
+    Exact,
+    /// Breadth-first dynamic program over (end letter, letter mask) pairs, always uses the
+    /// fewest words.
+    Dp,
+    /// Always play the word covering the most new letters, may find no solution at all.
+    Greedy,
+    /// Breadth-first search keeping only the best states of each layer.
+    Beam,
+    /// Exact depth-first search split across threads by first word.
+    Parallel,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Hardness {
+    /// The shortest solution uses as many words as possible.
+    MinWords,
+    /// As few solutions as possible use the fewest words.
+    FewestSolutions,
+    /// As few words as possible can be played.
+    FewestWords,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Objective {
+    /// Cover the most letters with the fewest letters typed.
+    Coverage,
+    /// Use as few words as possible.
+    Words,
+    /// Type as few letters as possible.
+    Letters,
+    /// Type as few letters more than once as possible.
+    Repeats,
+}
+
+impl Objective {
+    fn scorer(self) -> Box<dyn Scorer> {
+        match self {
+            Objective::Coverage => Box::new(Coverage),
+            Objective::Words => Box::new(FewestWords),
+            Objective::Letters => Box::new(FewestLetters),
+            Objective::Repeats => Box::new(FewestRepeats),
+        }
+    }
+
+    /// scorer for a list of objectives, where later objectives break ties in earlier ones
+    fn combined(objectives: &[Objective]) -> Box<dyn Scorer> {
+        match objectives {
+            [objective] => objective.scorer(),
+            _ => Box::new(Lexicographic(
+                objectives.iter().map(|o| o.scorer()).collect(),
+            )),
+        }
+    }
+}
+
+impl Strategy {
+    fn solver(self, cli: &Cli) -> Box<dyn Solver> {
+        match self {
+            Strategy::Astar => Box::new(AStar {
+                scorer: Objective::combined(&cli.optimize),
+            }),
+            Strategy::Exact => Box::new(MinimalAStar),
+            Strategy::Dp => Box::new(BreadthFirst),
+            Strategy::Greedy => Box::new(Greedy),
+            Strategy::Beam => Box::new(Beam {
+                width: cli.beam_width,
+            }),
+            Strategy::Parallel => Box::new(Parallel {
+                threads: cli.threads.unwrap_or(Parallel::default().threads),
+            }),
+        }
+    }
+}
 
 #[derive(Parser)]
+#[clap(subcommand_negates_reqs = true)]
 struct Cli {
+    #[clap(subcommand)]
+    command: Option<Command>,
     ///The 12 letters on the board, clockwise.
-    board_letters: String,
-    #[clap(short, long, value_hint = clap::ValueHint::FilePath)]
+    #[clap(required = true)]
+    board_letters: Option<String>,
+    #[clap(short, long, global = true, value_hint = clap::ValueHint::FilePath)]
     /// Use custom dictionary file.
     dictionary: Option<String>,
     /// Show the words that can be made with this board.
     #[clap(long)]
     show_words: bool,
+    /// Check which letters the words can cover and chain through before searching, and stop if
+    /// the board has no solution.
+    #[clap(long)]
+    analyze: bool,
+    /// Also show the solution as the continuous chain of letters typed in the game.
+    #[clap(long)]
+    chain: bool,
+    /// List every solution instead of stopping at the first one found.
+    #[clap(long)]
+    all: bool,
+    /// Find a solution with the fewest possible words, and report which shorter solutions were
+    /// ruled out. Same as --strategy exact.
+    #[clap(long, conflicts_with_all = ["all", "strategy"])]
+    exact: bool,
+    /// Show every solution on the trade-off between fewer words and fewer letters.
+    #[clap(long, conflicts_with_all = ["all", "exact", "strategy"])]
+    pareto: bool,
+    /// Print every better solution as the astar strategy finds it, until the search is done or a
+    /// limit is hit.
+    #[clap(long, conflicts_with_all = ["all", "exact", "strategy", "pareto"])]
+    stream: bool,
+    /// Show the best N solutions, ranked by fewest words and then fewest letters.
+    #[clap(long, value_name = "N", conflicts_with_all = ["all", "exact", "strategy", "pareto"])]
+    top: Option<usize>,
+    /// How different the solutions shown with --top must be: "any", "disjoint" for no shared
+    /// words, or the number of words each solution needs that no better one uses.
+    #[clap(long, default_value = "any", requires = "top")]
+    diversity: Diversity,
+    /// Search strategy used to find a solution.
+    #[clap(long, value_enum, default_value_t = Strategy::Astar, conflicts_with = "all")]
+    strategy: Strategy,
+    /// Maximum number of words in a solution [default: 3 with --all, 8 otherwise].
+    #[clap(long, global = true)]
+    max_words: Option<usize>,
+    /// Objectives the astar strategy optimizes, separated by commas. Later objectives break ties
+    /// in earlier ones.
+    #[clap(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "coverage",
+        conflicts_with = "exact"
+    )]
+    optimize: Vec<Objective>,
+    /// Number of states kept in each layer of the beam search.
+    #[clap(long, default_value_t = 64)]
+    beam_width: usize,
+    /// Number of threads used by the parallel strategy [default: number of CPUs].
+    #[clap(long)]
+    threads: Option<usize>,
+    /// Give up after expanding this many states.
+    #[clap(long)]
+    max_expanded: Option<usize>,
+    /// Give up once the search tracks this many states.
+    #[clap(long)]
+    max_states: Option<usize>,
+    /// Report the progress of the search on stderr.
+    #[clap(long)]
+    progress: bool,
+    /// Give up after this many seconds.
+    #[clap(long, value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+    /// Only look for solutions starting with this word.
+    #[clap(long, value_name = "WORD", conflicts_with = "all")]
+    start_with: Option<String>,
+    /// Only look for solutions using these words, separated by commas. At most 4 words.
+    #[clap(
+        long,
+        value_name = "WORDS",
+        value_delimiter = ',',
+        conflicts_with = "all"
+    )]
+    must_include: Vec<String>,
+    /// Never use these words, separated by commas.
+    #[clap(
+        long,
+        value_name = "WORDS",
+        value_delimiter = ',',
+        conflicts_with = "all"
+    )]
+    exclude: Vec<String>,
+    /// Never use the words in this file, one per line.
+    #[clap(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath, conflicts_with = "all")]
+    exclude_file: Option<String>,
+    /// Only look for solutions with exactly this many words.
+    #[clap(long, value_name = "N", conflicts_with = "all")]
+    exact_words: Option<usize>,
+    /// Only use words with at least this many letters.
+    #[clap(long, value_name = "N", conflicts_with = "all")]
+    min_word_len: Option<usize>,
+    /// Only use words with at most this many letters.
+    #[clap(long, value_name = "N", conflicts_with = "all")]
+    max_word_len: Option<usize>,
+    /// Only look for solutions with at most this many letters in total.
+    #[clap(long, value_name = "N", conflicts_with = "all")]
+    max_letters: Option<usize>,
+    /// When no solution is found, show the words covering the most letters instead, and why the
+    /// other letters can't be covered.
+    #[clap(long, conflicts_with_all = ["all", "pareto", "top", "stream"])]
+    partial: bool,
+    /// Show statistics about the search once it is done.
+    #[clap(long, conflicts_with = "all")]
+    stats: bool,
+    /// Write every state the search expands to this file, one per line: the last word played,
+    /// the letters covered as a bitmask and the score of the state.
+    #[clap(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath, conflicts_with = "all")]
+    trace: Option<String>,
 }
 
-fn main() {
-    // Get board letters from command-line arguments
-    let cli = Cli::parse();
-    let board_letters = cli.board_letters;
+#[derive(Subcommand)]
+enum Command {
+    /// Suggest words to play next, best first.
+    Hint {
+        ///The 12 letters on the board, clockwise.
+        board_letters: String,
+        /// The words played so far.
+        words: Vec<String>,
+        /// Number of suggestions to show.
+        #[clap(long, default_value_t = 5)]
+        count: usize,
+    },
+    /// Give clues to a solution with the fewest words, without spoiling all of it at once.
+    Clue {
+        ///The 12 letters on the board, clockwise.
+        board_letters: String,
+        /// How much to give away: 1 for the number of words, 2 adds their first letters, 3
+        /// their lengths, and every level after that reveals one more word.
+        #[clap(long, default_value_t = 1)]
+        level: usize,
+    },
+    /// Check a solution against the rules of the game.
+    Validate {
+        ///The 12 letters on the board, clockwise.
+        board_letters: String,
+        /// The words of the solution.
+        #[clap(required = true)]
+        words: Vec<String>,
+    },
+    /// Make random boards with a given par.
+    Generate {
+        /// Number of words of the shortest solution.
+        #[clap(long, default_value_t = 3)]
+        par: usize,
+        /// Fewest words that can be made with an accepted board.
+        #[clap(long, default_value_t = 50)]
+        min_legal: usize,
+        /// Most words that can be made with an accepted board.
+        #[clap(long, default_value_t = 1000)]
+        max_legal: usize,
+        /// Seed of the random number generator [default: the current time].
+        #[clap(long)]
+        seed: Option<u64>,
+        /// Number of boards to make.
+        #[clap(long, default_value_t = 1)]
+        count: usize,
+        /// Boards tried for each board made before giving up.
+        #[clap(long, default_value_t = 1000)]
+        attempts: usize,
+    },
+    /// Rearrange the letters of a board across its sides to make it as hard as possible, while
+    /// keeping it solvable.
+    Harden {
+        ///The 12 letters on the board, clockwise.
+        board_letters: String,
+        /// What makes the board hard.
+        #[clap(long, value_enum, default_value_t = Hardness::MinWords)]
+        objective: Hardness,
+        /// Number of letter swaps tried.
+        #[clap(long, default_value_t = 200)]
+        iterations: usize,
+        /// Starting temperature of the simulated annealing, 0 for plain hill climbing.
+        #[clap(long, default_value_t = 1.0)]
+        temperature: f64,
+        /// Most solutions counted with --objective fewest-solutions.
+        #[clap(long, default_value_t = 100)]
+        max_solutions: usize,
+        /// Seed of the random number generator [default: the current time].
+        #[clap(long)]
+        seed: Option<u64>,
+        /// Append the hardest board to this puzzle file, one tab separated line of the letters,
+        /// the par, the number of legal words and the solution.
+        #[clap(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+        output: Option<String>,
+    },
+    /// Rate how hard a board is from 0 to 100, and tag it easy, medium or hard.
+    Rate {
+        ///The 12 letters on the board, clockwise.
+        board_letters: String,
+        /// Most minimal solutions counted.
+        #[clap(long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+        max_solutions: u64,
+    },
+    /// Check whether a board has a single solution with the fewest words, or count the others.
+    Unique {
+        ///The 12 letters on the board, clockwise.
+        board_letters: String,
+        /// Stop once this many solutions with the fewest words are found.
+        #[clap(long, value_parser = clap::value_parser!(u64).range(1..))]
+        limit: Option<u64>,
+        /// List each solution with its junction letters, where one word ends and the next starts,
+        /// and how many solutions pass through each sequence of junctions.
+        #[clap(long)]
+        junctions: bool,
+    },
+    /// Split a continuous chain of letters into words, in every possible way.
+    Segment {
+        ///The 12 letters on the board, clockwise.
+        board_letters: String,
+        /// The letters typed, with the letter shared by consecutive words written once, eg:
+        /// SENTRYESELFUNDSWUNG.
+        chain: String,
+    },
+}
+
+/// positive number of seconds
+fn parse_timeout(arg: &str) -> Result<Duration, String> {
+    let secs: f64 = arg.parse().map_err(|e| format!("{}", e))?;
+    match Duration::try_from_secs_f64(secs) {
+        Ok(timeout) if !timeout.is_zero() => Ok(timeout),
+        _ => Err(format!("{} is not a positive number of seconds", arg)),
+    }
+}
+
+fn format_solution(solution: Vec<String>) -> String {
+    solution
+        .into_iter()
+        .map(|s| s.to_uppercase())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// `count` followed by `noun`, with an s unless `count` is 1
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+fn print_stats(stats: &SearchStats) {
+    println!("Search statistics:");
+    println!("  states generated:     {}", stats.generated);
+    println!("  states expanded:      {}", stats.expanded);
+    println!("  duplicate states:     {}", stats.duplicates);
+    println!("  peak states tracked:  {}", stats.peak_parent_size);
+    println!("  peak queue length:    {}", stats.peak_queue_len);
+    println!(
+        "  time: {:.3}s setup, {:.3}s search, {:.3}s reconstruct",
+        stats.setup_time.as_secs_f64(),
+        stats.search_time.as_secs_f64(),
+        stats.reconstruct_time.as_secs_f64()
+    );
+}
+
+/// Describe the shortest solutions that were proven not to exist, eg: "No 1- or 2-word solution
+/// exists."
+fn format_ruled_out(ruled_out: usize, options: &SolveOptions) -> Option<String> {
+    let proof = match ruled_out {
+        0 => return None,
+        1 => "No 1-word solution exists".to_string(),
+        2 => "No 1- or 2-word solution exists".to_string(),
+        n => format!("No solution with {} words or fewer exists", n),
+    };
+    Some(qualify(proof, options))
+}
+
+/// End a claim about every solution, limiting it to the constraints it was proven under.
+fn qualify(claim: String, options: &SolveOptions) -> String {
+    if options.constrained() {
+        format!("{} with the given constraints.", claim)
+    } else {
+        format!("{}.", claim)
+    }
+}
 
-    let board = Board::from(board_letters.chars());
+/// Show the board and load the words that can be played on it.
+fn load_board(board_letters: &str, cli: &Cli) -> (Board, Trie) {
+    let board = Board::from(board_letters.to_lowercase().chars());
     board.show();
 
-    let words = match &cli.dictionary {
+    let trie = Trie::new_with_board(read_words(&read_dictionary(cli)), &board);
+    (board, trie)
+}
+
+/// seed of the random number generator when none is given
+fn time_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+/// text of the dictionary chosen on the command line
+fn read_dictionary(cli: &Cli) -> String {
+    match &cli.dictionary {
         Some(path) => std::fs::read_to_string(path).expect("Could not read file"),
-        None => include_str!("2of12.txt").to_string(),
-    };
+        None => DEFAULT_WORDS.to_string(),
+    }
+}
 
-    let words = words.lines().filter_map(|l| {
-        if l.len() < 3 || l.ends_with("'s") {
-            None
-        } else {
-            Some(l.trim().to_lowercase())
+fn run_command(command: &Command, cli: &Cli) {
+    match command {
+        Command::Hint {
+            board_letters,
+            words,
+            count,
+        } => {
+            let (board, trie) = load_board(board_letters, cli);
+            let options = SolveOptions {
+                max_words: cli.max_words.unwrap_or(8),
+                ..Default::default()
+            };
+            let played: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+            match hint(&board, &trie, &played, &options) {
+                Ok(hints) if hints.is_empty() => {
+                    let covered = played.iter().fold(0, |mask, w| mask | board.get_mask(w));
+                    if covered == FULL_MASK {
+                        println!("Every letter is already covered.");
+                    } else {
+                        println!("No word can be played next.");
+                    }
+                }
+                Ok(hints) => {
+                    for (rank, hint) in hints.iter().take(*count).enumerate() {
+                        let finish = match hint.solution_words {
+                            Some(n) => format!("solvable in {}", plural(n, "word")),
+                            None => {
+                                format!("no solution within {}", plural(options.max_words, "word"))
+                            }
+                        };
+                        println!(
+                            "{:>3}. {:<15} {} new letter{}, {}",
+                            rank + 1,
+                            hint.word.to_uppercase(),
+                            hint.new_letters,
+                            if hint.new_letters == 1 { "" } else { "s" },
+                            finish
+                        );
+                    }
+                }
+                Err(e) => {
+                    println!("No hint: {}", e);
+                    std::process::exit(1);
+                }
+            }
         }
-    });
+        Command::Clue {
+            board_letters,
+            level,
+        } => {
+            let (board, trie) = load_board(board_letters, cli);
+            let options = SolveOptions {
+                max_words: cli.max_words.unwrap_or(8),
+                ..Default::default()
+            };
+            match solve_minimal(&board, &trie, &options) {
+                Ok(solution) => {
+                    for clue in clues(&solution, *level) {
+                        println!("{}", clue);
+                    }
+                }
+                Err(e) => {
+                    println!("No solution found: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Generate {
+            par,
+            min_legal,
+            max_legal,
+            seed,
+            count,
+            attempts,
+        } => {
+            let seed = seed.unwrap_or_else(time_seed);
+            println!("Seed: {}", seed);
+            let words: Vec<String> = read_words(&read_dictionary(cli)).collect();
+            let options = GenerateOptions {
+                par: *par,
+                legal_words: *min_legal..=*max_legal,
+                attempts: *attempts,
+            };
+            let mut rng = Rng::new(seed);
+            for _ in 0..*count {
+                let Some(puzzle) = generate(&words, &mut rng, &options) else {
+                    println!("No board found within {}.", plural(*attempts, "attempt"));
+                    std::process::exit(1);
+                };
+                println!();
+                puzzle.board.show();
+                println!(
+                    "Board {}: {} can be made, par {}: {}",
+                    puzzle
+                        .board
+                        .letters
+                        .iter()
+                        .collect::<String>()
+                        .to_uppercase(),
+                    plural(puzzle.legal_words, "word"),
+                    par,
+                    format_solution(puzzle.solution.words)
+                );
+            }
+        }
+        Command::Harden {
+            board_letters,
+            objective,
+            iterations,
+            temperature,
+            max_solutions,
+            seed,
+            output,
+        } => {
+            let seed = seed.unwrap_or_else(time_seed);
+            println!("Seed: {}", seed);
+            let start = Board::from(board_letters.to_lowercase().chars());
+            start.show();
+            let words: Vec<String> = read_words(&read_dictionary(cli)).collect();
+            let options = HardenOptions {
+                difficulty: match objective {
+                    Hardness::MinWords => Difficulty::MinWords,
+                    Hardness::FewestSolutions => Difficulty::FewestSolutions,
+                    Hardness::FewestWords => Difficulty::FewestWords,
+                },
+                iterations: *iterations,
+                temperature: *temperature,
+                max_words: cli.max_words.unwrap_or(6),
+                max_solutions: *max_solutions,
+            };
+            let Some((_, puzzle)) = harden(&words, &start, &mut Rng::new(seed), &options) else {
+                println!(
+                    "The board has no solution within {}.",
+                    plural(options.max_words, "word")
+                );
+                std::process::exit(1);
+            };
+            puzzle.board.show();
+            println!(
+                "Hardest board {}: {} can be made, par {}: {}",
+                puzzle
+                    .board
+                    .letters
+                    .iter()
+                    .collect::<String>()
+                    .to_uppercase(),
+                plural(puzzle.legal_words, "word"),
+                puzzle.solution.words.len(),
+                format_solution(puzzle.solution.words.clone())
+            );
+            if let Some(path) = output {
+                let mut file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .expect("Could not open puzzle file");
+                writeln!(file, "{}", puzzle).expect("Could not write puzzle file");
+            }
+        }
+        Command::Rate {
+            board_letters,
+            max_solutions,
+        } => {
+            let board = Board::from(board_letters.to_lowercase().chars());
+            board.show();
+            let words: Vec<String> = read_words(&read_dictionary(cli)).collect();
+            let options = RateOptions {
+                max_words: cli.max_words.unwrap_or(8),
+                max_solutions: *max_solutions as usize,
+            };
+            let rating = match rate(&words, &board, &options) {
+                Ok(rating) => rating,
+                Err(e) => {
+                    println!("Can't rate the board: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let solutions = if rating.solutions == options.max_solutions {
+                format!("{}+ minimal solutions", rating.solutions)
+            } else {
+                plural(rating.solutions, "minimal solution")
+            };
+            println!(
+                "{}, par {}, {}, letter rarity {:.2}, {:.1} letters per word",
+                plural(rating.legal_words, "legal word"),
+                rating.par,
+                solutions,
+                rating.letter_rarity,
+                rating.average_word_len
+            );
+            for component in &rating.components {
+                println!(
+                    "  {:<16} {:5.1} / {:.0}",
+                    component.name,
+                    component.points(),
+                    component.weight
+                );
+            }
+            println!("Score: {:.1} ({})", rating.score, rating.tag);
+        }
+        Command::Unique {
+            board_letters,
+            limit,
+            junctions: show_junctions,
+        } => {
+            let (board, trie) = load_board(board_letters, cli);
+            let options = SolveOptions {
+                max_words: cli.max_words.unwrap_or(8),
+                ..Default::default()
+            };
+            let found = match minimal_solutions(&board, &trie, &options, limit.map(|l| l as usize))
+            {
+                Ok(found) => found,
+                Err(e) => {
+                    println!("{}", e);
+                    std::process::exit(1);
+                }
+            };
+            if *show_junctions {
+                for words in &found.solutions {
+                    let letters: String = junctions(words).into_iter().collect();
+                    println!(
+                        "{}: {}",
+                        letters.to_uppercase(),
+                        format_solution(words.clone())
+                    );
+                }
+                println!("Junctions:");
+                for (letters, count) in found.junctions() {
+                    let letters: String = letters.into_iter().collect();
+                    println!(
+                        "  {}: {}",
+                        letters.to_uppercase(),
+                        plural(count, "solution")
+                    );
+                }
+            }
+            let more = if found.truncated { "more than " } else { "" };
+            println!(
+                "Found {}{} with {}.",
+                more,
+                plural(found.solutions.len(), "solution"),
+                plural(found.par, "word")
+            );
+            if found.is_unique() {
+                println!("The solution is unique.");
+            }
+        }
+        Command::Segment {
+            board_letters,
+            chain,
+        } => {
+            let (_, trie) = load_board(board_letters, cli);
+            let segmentations = segment(&trie, &chain.to_lowercase());
+            for words in &segmentations {
+                println!("{}", format_solution(words.clone()));
+            }
+            println!(
+                "Found {} to split the chain.",
+                plural(segmentations.len(), "way")
+            );
+            if segmentations.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::Validate {
+            board_letters,
+            words,
+        } => {
+            let (board, trie) = load_board(board_letters, cli);
+            let words: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+            match validate(&board, &trie, &words) {
+                Ok(()) => println!("Valid solution: {}", format_solution(words)),
+                Err(violations) => {
+                    println!("Invalid solution:");
+                    for violation in violations {
+                        println!("  {}", violation);
+                    }
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
 
-    let trie = Trie::new_with_board(words, &board);
+fn main() {
+    // Get board letters from command-line arguments
+    let cli = Cli::parse();
+    if let Some(command) = &cli.command {
+        run_command(command, &cli);
+        return;
+    }
+
+    let (board, trie) = load_board(cli.board_letters.as_deref().unwrap(), &cli);
     if cli.show_words {
         for word in trie.iter() {
             println!("{}", word);
         }
     }
     println!(
-        "There are {} words that can be made with this board.",
-        trie.len()
+        "There are {} that can be made with this board.",
+        plural(trie.len(), "word")
     );
 
-    let answer = solve(&board, &trie);
-    let mut code = 0;
-    if let Some(solution) = answer {
-        let solution = solution
-            .into_iter()
-            .map(|s| s.to_uppercase())
-            .collect::<Vec<String>>()
-            .join(", ");
-        println!("Found solution: {}", solution);
+    if cli.analyze {
+        let analysis = analyze(&board, &trie);
+        let letters = |letters: &[char]| -> String {
+            match letters {
+                [] => "none".into(),
+                _ => letters
+                    .iter()
+                    .map(|c| c.to_ascii_uppercase().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            }
+        };
+        println!("Letters no word uses: {}", letters(&analysis.unused));
+        println!(
+            "Letters no word starts with: {}",
+            letters(&analysis.never_start)
+        );
+        println!(
+            "Letters no word ends with: {}",
+            letters(&analysis.never_end)
+        );
+        println!(
+            "Words ending with these letters can only be played last: {}",
+            letters(&analysis.dead_ends)
+        );
+        if let Some(n) = analysis.min_words {
+            println!("A solution needs at least {}.", plural(n, "word"));
+        }
+        if analysis.impossible() {
+            println!("The board has no solution.");
+            std::process::exit(1);
+        }
+    }
+
+    if cli.all {
+        let max_words = cli.max_words.unwrap_or(3);
+        let mut count = 0;
+        for solution in solve_all(&board, &trie, max_words) {
+            println!("{}", format_solution(solution));
+            count += 1;
+        }
+        println!(
+            "Found {} with {} or fewer.",
+            plural(count, "solution"),
+            plural(max_words, "word")
+        );
+        std::process::exit(if count == 0 { 1 } else { 0 });
+    }
+
+    let strategy = if cli.exact {
+        Strategy::Exact
+    } else {
+        cli.strategy
+    };
+    if cli.must_include.len() > MAX_REQUIRED_WORDS {
+        Cli::command()
+            .error(
+                ErrorKind::TooManyValues,
+                format!("at most {} words can be required", MAX_REQUIRED_WORDS),
+            )
+            .exit();
+    }
+    let mut exclude: HashSet<String> = cli.exclude.iter().map(|w| w.to_lowercase()).collect();
+    if let Some(path) = &cli.exclude_file {
+        let text = std::fs::read_to_string(path).expect("Could not read file");
+        exclude.extend(
+            text.lines()
+                .map(|w| w.trim().to_lowercase())
+                .filter(|w| !w.is_empty()),
+        );
+    }
+    let stats = cli
+        .stats
+        .then(|| Arc::new(Mutex::new(SearchStats::default())));
+    let trace = cli.trace.as_ref().map(|path| {
+        let file = File::create(path).expect("Could not create trace file");
+        Arc::new(Mutex::new(BufWriter::new(file)))
+    });
+    let options = SolveOptions {
+        max_words: cli.max_words.unwrap_or(8),
+        max_expanded: cli.max_expanded,
+        max_parent_size: cli.max_states,
+        // a timeout too far away to be represented never runs out
+        deadline: cli.timeout.and_then(|t| Instant::now().checked_add(t)),
+        cancel: None,
+        progress: cli.progress.then(|| {
+            Arc::new(|p: &Progress| {
+                eprintln!(
+                    "[{:>8.3}s] {} states expanded, {} queued, {}/{} letters covered",
+                    p.elapsed.as_secs_f64(),
+                    p.expanded,
+                    p.queue_len,
+                    p.best_coverage,
+                    BOARD_LEN
+                )
+            }) as ProgressCallback
+        }),
+        progress_interval: 10_000,
+        start_with: cli.start_with.as_ref().map(|w| w.to_lowercase()),
+        must_include: cli.must_include.iter().map(|w| w.to_lowercase()).collect(),
+        exclude,
+        exact_words: cli.exact_words,
+        min_word_len: cli.min_word_len,
+        max_word_len: cli.max_word_len,
+        max_letters: cli.max_letters,
+        stats: stats.clone(),
+        trace: trace.clone().map(|t| t as Arc<Mutex<dyn Write + Send>>),
+    };
+
+    let solved = if cli.pareto {
+        match pareto_front(&board, &trie, &options) {
+            Ok(front) => {
+                println!("{:>5}  {:>7}  Solution", "Words", "Letters");
+                for solution in front {
+                    println!(
+                        "{:>5}  {:>7}  {}",
+                        solution.words.len(),
+                        solution.total_letters(),
+                        format_solution(solution.words)
+                    );
+                }
+                true
+            }
+            Err(e) => {
+                println!("No solution found: {}", e);
+                false
+            }
+        }
+    } else if let Some(k) = cli.top {
+        match solve_top_k(&board, &trie, k, cli.diversity, &options) {
+            Ok(solutions) => {
+                for (rank, solution) in solutions.into_iter().enumerate() {
+                    println!("{:>3}. {}", rank + 1, format_solution(solution.words));
+                }
+                true
+            }
+            Err(e) => {
+                println!("No solution found: {}", e);
+                false
+            }
+        }
+    } else if cli.stream {
+        let scorer = Objective::combined(&cli.optimize);
+        let result = solve_anytime(&board, &trie, &options, scorer.as_ref(), |improvement| {
+            println!(
+                "[{:>8.3}s {:>8} states] {}",
+                improvement.elapsed.as_secs_f64(),
+                improvement.expanded,
+                format_solution(improvement.solution.words.clone())
+            );
+        });
+        if let Err(e) = &result {
+            println!("No solution found: {}", e);
+        }
+        result.is_ok()
     } else {
-        println!("No solution found");
-        code = 1;
+        let solver = strategy.solver(&cli);
+        let solved = match solver.solve(&board, &trie, &options) {
+            Ok(solution) => {
+                if let Some(proof) = format_ruled_out(solution.ruled_out, &options) {
+                    println!("{}", proof);
+                }
+                let chain = to_chain(&solution.words).to_uppercase();
+                println!("Found solution: {}", format_solution(solution.words));
+                if cli.chain {
+                    println!("Chain: {}", chain);
+                }
+                true
+            }
+            Err(SolveError::Unsolvable { max_words }) => {
+                let proof = match options.exact_words {
+                    Some(n) if n <= max_words => Some(qualify(
+                        format!("No solution with exactly {} exists", plural(n, "word")),
+                        &options,
+                    )),
+                    _ => format_ruled_out(max_words, &options),
+                };
+                if let Some(proof) = proof {
+                    println!("{}", proof);
+                }
+                println!("No solution found");
+                false
+            }
+            Err(e) => {
+                println!("No solution found: {}", e);
+                false
+            }
+        };
+        if !solved && cli.partial {
+            match best_partial(&board, &trie, &options) {
+                Ok(partial) => {
+                    println!(
+                        "Best partial solution: {} ({}/{} letters)",
+                        format_solution(partial.words),
+                        BOARD_LEN - partial.uncovered.len(),
+                        BOARD_LEN
+                    );
+                    for (letter, reason) in partial.uncovered {
+                        println!("  {}: {}", letter.to_ascii_uppercase(), reason);
+                    }
+                }
+                Err(e) => println!("No partial solution found: {}", e),
+            }
+        }
+        solved
+    };
+
+    if let Some(stats) = stats {
+        print_stats(&stats.lock().unwrap());
+    }
+    if let Some(trace) = trace {
+        trace
+            .lock()
+            .unwrap()
+            .flush()
+            .expect("Could not write trace file");
     }
-    std::process::exit(code);
+    std::process::exit(if solved { 0 } else { 1 });
 }
@@ -0,0 +1,220 @@
+use crate::board::{Board, BOARD_LEN};
+use crate::generate::letter_weights;
+use crate::solver::{minimal_solutions, SolveError, SolveOptions};
+use crate::trie::Trie;
+use std::fmt;
+
+/// Legal word counts at or above this rate as an easy board.
+const MANY_WORDS: f64 = 2000.0;
+/// Average solution word lengths at or above this rate as hard.
+const LONG_WORD: f64 = 10.0;
+
+/// How [`rate`] solves the board
+#[derive(Debug, Clone)]
+pub struct RateOptions {
+    /// most words of a solution, boards needing more can't be rated
+    pub max_words: usize,
+    /// minimal solutions counted at most, at least one is always counted
+    pub max_solutions: usize,
+}
+
+impl Default for RateOptions {
+    fn default() -> Self {
+        RateOptions {
+            max_words: 8,
+            max_solutions: 100,
+        }
+    }
+}
+
+/// Difficulty tag of a rated board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tag {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Tag {
+    fn from_score(score: f64) -> Self {
+        if score < 35.0 {
+            Tag::Easy
+        } else if score < 60.0 {
+            Tag::Medium
+        } else {
+            Tag::Hard
+        }
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tag::Easy => write!(f, "easy"),
+            Tag::Medium => write!(f, "medium"),
+            Tag::Hard => write!(f, "hard"),
+        }
+    }
+}
+
+/// One part of a [`Rating`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Component {
+    pub name: &'static str,
+    /// how hard this part makes the board, from 0 to 1
+    pub difficulty: f64,
+    /// points the component adds to the score at difficulty 1
+    pub weight: f64,
+}
+
+impl Component {
+    pub fn points(&self) -> f64 {
+        self.difficulty * self.weight
+    }
+}
+
+/// How hard a board is, found by [`rate`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rating {
+    /// number of words that can be played on the board
+    pub legal_words: usize,
+    /// number of words of the shortest solution
+    pub par: usize,
+    /// number of distinct solutions with `par` words, up to `RateOptions::max_solutions`
+    pub solutions: usize,
+    /// how rare the board letters are in the dictionary, from 0 to 1
+    pub letter_rarity: f64,
+    /// average length of the words of the easiest solution
+    pub average_word_len: f64,
+    pub components: Vec<Component>,
+    /// sum of the points of the components, from 0 to 100
+    pub score: f64,
+    pub tag: Tag,
+}
+
+/// Rate how hard a board is from 0 to 100, using the words of a dictionary.
+///
+/// The score adds up:
+/// - par: boards needing more words are harder, from 1 word up to 5
+/// - legal words: fewer words to pick from is harder, on a log scale up to 2000 words
+/// - solutions: a unique minimal solution is hardest, `n` minimal solutions rate `1 / n`
+/// - letter rarity: one minus the average frequency of the board letters in the dictionary,
+///   relative to its most common letter
+/// - word length: longer words are harder to think of, rated on the average length of the words
+///   of the easiest solution from 3 letters up to 10. The dictionary has no word frequencies, so
+///   this also stands in for how uncommon the words are
+///
+/// The easiest solution is the minimal solution with the fewest letters among those counted.
+pub fn rate(words: &[String], board: &Board, options: &RateOptions) -> Result<Rating, SolveError> {
+    let trie = Trie::new_with_board(words, board);
+    let solve_options = SolveOptions {
+        max_words: options.max_words,
+        ..Default::default()
+    };
+    // at least one solution is needed to rate the words of the easiest one
+    let found = minimal_solutions(
+        board,
+        &trie,
+        &solve_options,
+        Some(options.max_solutions.max(1)),
+    )?;
+    let par = found.par;
+    let solutions = found.solutions.len();
+    let easiest_letters = found
+        .solutions
+        .iter()
+        .map(|words| words.iter().map(|w| w.chars().count()).sum::<usize>())
+        .min()
+        .unwrap();
+
+    let weights = letter_weights(words);
+    let most_common = *weights.iter().max().unwrap() as f64;
+    let frequency: f64 = board
+        .letters
+        .iter()
+        .filter(|c| c.is_ascii_lowercase())
+        .map(|&c| weights[(c as u8 - b'a') as usize] as f64 / most_common)
+        .sum();
+    let letter_rarity = 1.0 - frequency / BOARD_LEN as f64;
+    let average_word_len = easiest_letters as f64 / par as f64;
+
+    let legal_words = trie.len();
+    let components = vec![
+        Component {
+            name: "par",
+            difficulty: ((par as f64 - 1.0) / 4.0).min(1.0),
+            weight: 30.0,
+        },
+        Component {
+            name: "legal words",
+            difficulty: 1.0 - ((legal_words as f64).ln_1p() / MANY_WORDS.ln_1p()).min(1.0),
+            weight: 20.0,
+        },
+        Component {
+            name: "solutions",
+            difficulty: 1.0 / solutions as f64,
+            weight: 20.0,
+        },
+        Component {
+            name: "letter rarity",
+            difficulty: letter_rarity,
+            weight: 15.0,
+        },
+        Component {
+            name: "word length",
+            difficulty: ((average_word_len - 3.0) / (LONG_WORD - 3.0)).clamp(0.0, 1.0),
+            weight: 15.0,
+        },
+    ];
+    let score = components.iter().map(Component::points).sum();
+    Ok(Rating {
+        legal_words,
+        par,
+        solutions,
+        letter_rarity,
+        average_word_len,
+        components,
+        score,
+        tag: Tag::from_score(score),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::trie::{read_words, DEFAULT_WORDS};
+
+    #[test]
+    fn rate_boards() {
+        let words: Vec<String> = read_words(DEFAULT_WORDS).collect();
+        let options = RateOptions::default();
+        let rating = rate(&words, &Board::from("vkspyielurao".chars()), &options).unwrap();
+        assert_eq!(rating.par, 2);
+        assert!(rating.solutions >= 1);
+        assert_eq!(rating.components.len(), 5);
+        let total: f64 = rating.components.iter().map(|c| c.weight).sum();
+        assert_eq!(total, 100.0);
+        assert!((0.0..=100.0).contains(&rating.score));
+        assert_eq!(rating.tag, Tag::from_score(rating.score));
+
+        // the same board with fewer words to pick from is harder
+        let few: Vec<String> = ["previously", "yak", "kayo", "perilously"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let harder = rate(&few, &Board::from("vkspyielurao".chars()), &options).unwrap();
+        assert_eq!(harder.solutions, 1);
+        assert!(harder.score > rating.score);
+
+        // a single solution is counted even without room for any
+        let options = RateOptions {
+            max_solutions: 0,
+            ..Default::default()
+        };
+        let capped = rate(&words, &Board::from("vkspyielurao".chars()), &options).unwrap();
+        assert_eq!(capped.solutions, 1);
+        assert!((0.0..=100.0).contains(&capped.score));
+
+        assert!(rate(&few[..1], &Board::from("vkspyielurao".chars()), &options).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+/// Small seeded pseudo random number generator (SplitMix64), so that generated boards can be
+/// reproduced from their seed without depending on an external crate.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// uniform number in `0..n`, `n` must not be 0
+    pub fn below(&mut self, n: usize) -> usize {
+        // the bias of the modulo is negligible for the small ranges used here
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// uniform float in `0.0..1.0`
+    pub fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Index picked with probability proportional to its weight. `weights` must not all be 0.
+    pub fn weighted(&mut self, weights: &[usize]) -> usize {
+        let mut pick = self.below(weights.iter().sum());
+        for (i, &weight) in weights.iter().enumerate() {
+            if pick < weight {
+                return i;
+            }
+            pick -= weight;
+        }
+        unreachable!("pick is below the total weight")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn seeded_sequence() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+        for _ in 0..100 {
+            assert!(a.below(12) < 12);
+            assert!((0.0..1.0).contains(&a.unit()));
+            assert_eq!(a.weighted(&[0, 3, 0]), 1);
+        }
+    }
+}
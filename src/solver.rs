@@ -1,135 +1,730 @@
-use crate::board::{Board, BOARD_LEN, SIDE_LEN};
+use crate::board::{Board, BOARD_LEN, FULL_MASK};
 use crate::non_nan::OrderedF32; // solve requires f32's to be orderable'
 use crate::trie::Trie;
+use indexmap::map::Entry;
 use indexmap::IndexMap;
+use std::cmp::Reverse;
 use std::collections::BinaryHeap;
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
 
-const MAX_PATH_LEN: usize = 8;
+mod fallback;
+mod hint;
+mod options;
+mod parallel;
+mod pareto;
+mod scorer;
+mod strategy;
+mod top_k;
+mod unique;
+
+pub use fallback::{best_partial, BestPartial, UncoveredReason};
+pub use hint::{clues, hint, Clue, Hint, HintError};
+use options::{Budget, Phase};
+pub use options::{
+    CancelToken, Limit, Progress, ProgressCallback, SearchStats, SolveError, SolveOptions,
+};
+pub use parallel::Parallel;
+pub use pareto::pareto_front;
+use scorer::coverage_ratio;
+pub use scorer::{
+    Coverage, FewestLetters, FewestRepeats, FewestWords, Lexicographic, Partial, Score, Scorer,
+};
+pub use strategy::{all_solvers, AStar, Beam, BreadthFirst, Greedy, MinimalAStar, Solver};
+pub use top_k::{solve_top_k, Diversity};
+pub use unique::{junctions, minimal_solutions, MinimalSolutions};
+
+/// A solution to a board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub words: Vec<String>,
+    /// No solution exists with this many words or fewer. Searches that don't prove anything
+    /// leave this at 0.
+    pub ruled_out: usize,
+}
+
+impl Solution {
+    /// total number of letters typed across every word
+    pub fn total_letters(&self) -> usize {
+        self.words.iter().map(|w| w.chars().count()).sum()
+    }
+}
+
+impl From<Vec<String>> for Solution {
+    fn from(words: Vec<String>) -> Self {
+        Solution {
+            words,
+            ruled_out: 0,
+        }
+    }
+}
 
 /// the location on the board that the current state is located.
-#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
 enum Location {
     Root,
     Idx(usize),
 }
 
+/// Most words [`SolveOptions::must_include`] may hold, one for every bit of a state mask past
+/// the board letters.
+pub const MAX_REQUIRED_WORDS: usize = u16::BITS as usize - BOARD_LEN;
+
+/// State mask of a finished game: every board letter and every required word is covered.
+const GOAL_MASK: u16 = u16::MAX;
+
+/// The words that can be played on a board, referred to by id during the search.
+#[derive(Debug)]
+struct WordTable {
+    words: Vec<String>,
+    /// Letters of the board covered by each word. Bit `BOARD_LEN + i` is set if the word is
+    /// `must_include[i]`, and by every word if there is no such required word, so that a state
+    /// reaches [`GOAL_MASK`] once every letter and every required word is covered.
+    masks: Vec<u16>,
+    /// board index of the last letter of each word
+    ends: Vec<usize>,
+    /// ids of the words a solution may start with, in trie order
+    first_words: Vec<usize>,
+    /// ids of the words starting with each board letter, in trie order
+    starting_with: [Vec<usize>; BOARD_LEN],
+    /// number of words every solution has to have
+    exact_words: Option<usize>,
+    /// most letters a solution may have
+    max_letters: Option<usize>,
+}
+
+impl WordTable {
+    /// Table of the words of `trie` that the constraints of `options` allow.
+    fn new(board: &Board, trie: &Trie, options: &SolveOptions) -> Self {
+        let required = &options.must_include;
+        assert!(
+            required.len() <= MAX_REQUIRED_WORDS,
+            "at most {} required words are supported",
+            MAX_REQUIRED_WORDS
+        );
+        let unused_slots = (!((1u32 << (BOARD_LEN + required.len())) - 1)) as u16;
+        let mut table = WordTable {
+            words: vec![],
+            masks: vec![],
+            ends: vec![],
+            first_words: vec![],
+            starting_with: Default::default(),
+            exact_words: options.exact_words,
+            max_letters: options.max_letters,
+        };
+        for word in trie.iter() {
+            let len = word.chars().count();
+            if options.exclude.contains(&word)
+                || options.min_word_len.is_some_and(|min| len < min)
+                || options.max_word_len.is_some_and(|max| len > max)
+            {
+                continue;
+            }
+            let id = table.words.len();
+            let first = board.get_idx(word.chars().next().unwrap());
+            table.starting_with[first].push(id);
+            if options.start_with.as_ref().is_none_or(|w| *w == word) {
+                table.first_words.push(id);
+            }
+            let slots = required
+                .iter()
+                .enumerate()
+                .filter(|(_, w)| **w == word)
+                .fold(unused_slots, |mask, (i, _)| mask | 1 << (BOARD_LEN + i));
+            table.masks.push(board.get_mask(&word) | slots);
+            table.ends.push(board.get_idx(word.chars().last().unwrap()));
+            table.words.push(word);
+        }
+        table
+    }
+
+    /// Whether the constraints on the number of words and letters of a solution allow a path
+    /// reaching `state`. The game ends as soon as every letter is covered, so with an exact
+    /// number of words only goals with that many words are allowed.
+    fn allows(&self, state: &State) -> bool {
+        if self
+            .max_letters
+            .is_some_and(|max| state.total_letters > max)
+        {
+            return false;
+        }
+        match self.exact_words {
+            Some(words) if state.is_goal() => state.path_len == words,
+            Some(words) => state.path_len < words,
+            None => true,
+        }
+    }
+
+    /// the most letters a single word covers
+    fn max_cover(&self) -> usize {
+        self.masks
+            .iter()
+            .map(|m| (m & FULL_MASK).count_ones() as usize)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// ids of the words that may follow a state at the given location
+    fn next_words(&self, location: Location) -> Box<dyn Iterator<Item = usize> + '_> {
+        match location {
+            Location::Root => Box::new(self.first_words.iter().copied()),
+            Location::Idx(i) => Box::new(self.starting_with[i].iter().copied()),
+        }
+    }
+}
+
 /// Game state representation used in the A* search algorithm
-#[derive(Hash, Debug, Eq, PartialEq)]
-struct State<'b> {
-    board: &'b Board,
-    word: String,
+///
+/// Two states at the same location with the same letters covered can be finished by exactly the
+/// same words, so the search only keeps one of them. See [`State::key`].
+#[derive(Debug, Clone, Copy)]
+struct State {
+    /// id of the last word played, `None` for the starting state
+    word: Option<usize>,
     path_len: usize,
     total_letters: usize,
-    used_chars_mask: [bool; BOARD_LEN],
+    used_chars_mask: u16,
     location: Location,
 }
 
-impl<'b> State<'b> {
-    fn get_child_states(&self, trie: &Trie) -> Vec<State<'b>> {
-        if self.path_len >= MAX_PATH_LEN {
-            return vec![];
+impl State {
+    fn start() -> Self {
+        State {
+            word: None,
+            path_len: 0,
+            total_letters: 0,
+            used_chars_mask: 0,
+            location: Location::Root,
         }
-        let iter = match self.word.chars().last() {
-            Some(c) => trie.iter_from_prefix(&c.to_string()),
-            None => trie.iter(),
-        };
+    }
 
-        let required_starting_letter = self.word.chars().last();
-        // check if the next word is a legal letterboxed word
-        //  - Word starts with the last character of the current word
-        //  - Word starts with a character that is not on the same side of the board as the last character
-        let illegal = match required_starting_letter {
-            None => &self.board.letters[0..0],
-            Some(c) => {
-                let start_idx = self.board.get_idx(c) / SIDE_LEN;
-                &self.board.letters[start_idx..start_idx + SIDE_LEN]
-            }
-        };
+    /// Key the search deduplicates states on. When the constraints of `table` depend on the
+    /// number of words or letters of a path, paths differing in those aren't interchangeable
+    /// and they are part of the key.
+    fn key(&self, table: &WordTable) -> StateKey {
+        (
+            self.location,
+            self.used_chars_mask,
+            if table.exact_words.is_some() {
+                self.path_len
+            } else {
+                0
+            },
+            if table.max_letters.is_some() {
+                self.total_letters
+            } else {
+                0
+            },
+        )
+    }
 
-        iter.filter(|w| !illegal.contains(&w.chars().next().unwrap()))
-            .map(|word| {
-                let mut new_letter_mask = self.used_chars_mask;
-                for c in word.chars() {
-                    new_letter_mask[self.board.get_idx(c)] = true;
-                }
-                let final_letter_location = self.board.get_idx(word.chars().last().unwrap());
-                State {
-                    board: self.board,
-                    total_letters: self.total_letters + word.chars().count(),
-                    word,
-                    used_chars_mask: new_letter_mask,
-                    location: Location::Idx(final_letter_location),
-                    path_len: self.path_len + 1,
-                }
-            })
-            .collect()
+    /// Like [`State::key`], but always telling apart paths with different numbers of words.
+    /// Searches ranking paths on anything but their number of words need it: a path ranked
+    /// higher but with more words may be unable to finish within the word limit.
+    fn key_with_words(&self, table: &WordTable) -> StateKey {
+        let (location, mask, _, letters) = self.key(table);
+        (location, mask, self.path_len, letters)
+    }
+
+    /// the states reached by playing each word allowed next, see [`WordTable::allows`]
+    fn get_child_states<'t>(&self, table: &'t WordTable) -> impl Iterator<Item = State> + 't {
+        let parent = *self;
+        table
+            .next_words(self.location)
+            .map(move |id| parent.play(table, id))
+            .filter(|child| table.allows(child))
+    }
+
+    /// the last word played, `None` for the starting state
+    fn last_word<'t>(&self, table: &'t WordTable) -> Option<&'t str> {
+        self.word.map(|id| table.words[id].as_str())
+    }
+
+    /// the state reached by playing word `id` from this state
+    fn play(&self, table: &WordTable, id: usize) -> State {
+        State {
+            word: Some(id),
+            path_len: self.path_len + 1,
+            total_letters: self.total_letters + table.words[id].chars().count(),
+            used_chars_mask: self.used_chars_mask | table.masks[id],
+            location: Location::Idx(table.ends[id]),
+        }
     }
 
     fn calculate_score(&self) -> OrderedF32 {
-        // the score for a state is the rotio of used characters to total characters. +1 in the
-        // denominator is to avoid division by zero)
+        // the score for a state is the rotio of used characters to total characters.
+        OrderedF32(coverage_ratio(self.covered(), self.total_letters))
+    }
+
+    /// mask of the board letters covered, without the required words
+    fn covered(&self) -> u16 {
+        self.used_chars_mask & FULL_MASK
+    }
 
-        let f = (self.used_chars_mask.iter().filter(|&&b| b).count() as f32)
-            / (1 + self.total_letters) as f32;
-        OrderedF32(f)
+    /// the view of this state given to a [`Scorer`]
+    fn partial(&self, max_cover: usize) -> Partial {
+        Partial {
+            words: self.path_len,
+            letters: self.total_letters,
+            covered: self.covered(),
+            max_cover,
+        }
     }
 
     /// check if the current state is the target solution
     fn is_goal(&self) -> bool {
-        self.used_chars_mask.iter().all(|&f| f)
+        self.used_chars_mask == GOAL_MASK
+    }
+
+    /// Lower bound on the number of words still needed to finish from this state, given that no
+    /// single word covers more than `max_cover` distinct letters. Never overestimates. A state
+    /// covering every letter but missing a required word still needs a word.
+    fn remaining_words_bound(&self, max_cover: usize) -> usize {
+        let uncovered = (FULL_MASK & !self.covered()).count_ones() as usize;
+        let bound = uncovered.div_ceil(max_cover.max(1));
+        if self.is_goal() {
+            bound
+        } else {
+            bound.max(1)
+        }
+    }
+}
+
+/// Location, state mask, and the number of words and letters when they matter, see
+/// [`State::key`] and [`State::key_with_words`]
+type StateKey = (Location, u16, usize, usize);
+
+/// Search tree of visited states, keyed on [`State::key`]. Each entry holds the state reached
+/// with the fewest words for that key and the index of its parent.
+type ParentMap = IndexMap<StateKey, (State, usize)>;
+
+/// Record `state` as reached from the state at `parent_idx`. Returns the index of the state if it
+/// is new or was reached with fewer words than before, `None` if it isn't worth exploring again.
+fn visit(
+    parent: &mut ParentMap,
+    table: &WordTable,
+    state: State,
+    parent_idx: usize,
+) -> Option<usize> {
+    match parent.entry(state.key(table)) {
+        Entry::Occupied(mut entry) => {
+            if entry.get().0.path_len <= state.path_len {
+                return None;
+            }
+            entry.insert((state, parent_idx));
+            Some(entry.index())
+        }
+        Entry::Vacant(entry) => {
+            let idx = entry.index();
+            entry.insert((state, parent_idx));
+            Some(idx)
+        }
     }
 }
 
 /// Solve the letterboxed game with a given board and word list using the A* search algorithm,
 /// prioritizing efficiency
-pub fn solve(board: &Board, trie: &Trie) -> Option<Vec<String>> {
-    let start = State {
-        board,
-        word: "".into(),
-        path_len: 0,
-        total_letters: 0,
-        used_chars_mask: [false; BOARD_LEN],
-        location: Location::Root,
-    };
-    let mut parent = IndexMap::new();
+pub fn solve(board: &Board, trie: &Trie, options: &SolveOptions) -> Result<Solution, SolveError> {
+    solve_with(board, trie, options, &Coverage)
+}
+
+/// Solve the letterboxed game using the A* search algorithm, looking for the best solution
+/// according to `scorer`.
+///
+/// Of the paths reaching the same state with the same number of words only the one scoring
+/// highest is explored, so when the search runs out of states no solution within
+/// `options.max_words` exists.
+pub fn solve_with(
+    board: &Board,
+    trie: &Trie,
+    options: &SolveOptions,
+    scorer: &dyn Scorer,
+) -> Result<Solution, SolveError> {
+    let mut budget = Budget::new(options);
+    let table = WordTable::new(board, trie, options);
+    budget.phase(Phase::Search);
+    let mut found = None;
+    astar(&table, &mut budget, scorer, |parent, idx, _| {
+        found = Some(extract_path(parent, &table, idx));
+        ControlFlow::Break(())
+    })?;
+    found.map(Solution::from).ok_or(SolveError::Unsolvable {
+        max_words: options.max_words,
+    })
+}
+
+/// A solution found by [`solve_anytime`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Improvement {
+    pub solution: Solution,
+    /// time since the search started
+    pub elapsed: Duration,
+    /// states expanded so far
+    pub expanded: usize,
+}
+
+/// Like [`solve_with`], but keep searching after the first solution is found and report every
+/// solution scoring higher than the ones before it to `on_improvement` as soon as it is found.
+///
+/// Hitting a limit or being cancelled only ends the search early: the best solution found up to
+/// that point is returned.
+pub fn solve_anytime(
+    board: &Board,
+    trie: &Trie,
+    options: &SolveOptions,
+    scorer: &dyn Scorer,
+    mut on_improvement: impl FnMut(&Improvement),
+) -> Result<Solution, SolveError> {
+    let started = Instant::now();
+    let mut budget = Budget::new(options);
+    let table = WordTable::new(board, trie, options);
+    budget.phase(Phase::Search);
+    let max_cover = table.max_cover();
+    let mut best: Option<(Score, Solution)> = None;
+    let result = astar(&table, &mut budget, scorer, |parent, idx, expanded| {
+        let (_, (state, _)) = parent.get_index(idx).unwrap();
+        let score = scorer.score(&state.partial(max_cover));
+        if best.as_ref().is_none_or(|(best, _)| score > *best) {
+            let improvement = Improvement {
+                solution: extract_path(parent, &table, idx).into(),
+                elapsed: started.elapsed(),
+                expanded,
+            };
+            on_improvement(&improvement);
+            best = Some((score, improvement.solution));
+        }
+        ControlFlow::Continue(())
+    });
+    match (result, best) {
+        (Err(SolveError::LimitReached(_) | SolveError::Cancelled), Some((_, solution))) => {
+            Ok(solution)
+        }
+        (Err(e), _) => Err(e),
+        (Ok(()), Some((_, solution))) => Ok(solution),
+        (Ok(()), None) => Err(SolveError::Unsolvable {
+            max_words: options.max_words,
+        }),
+    }
+}
+
+/// The A* search behind [`solve_with`] and [`solve_anytime`]. `on_goal` is called with the parent
+/// map, the index of every goal state popped, and the number of states expanded so far, and
+/// decides whether the search goes on. Time spent in `on_goal` counts as reconstructing.
+///
+/// Of the paths reaching the same state with the same number of words only the one scoring
+/// highest is explored.
+fn astar(
+    table: &WordTable,
+    budget: &mut Budget,
+    scorer: &dyn Scorer,
+    mut on_goal: impl FnMut(&ParentMap, usize, usize) -> ControlFlow<()>,
+) -> Result<(), SolveError> {
+    let max_words = budget.options().max_words;
+    let max_cover = table.max_cover();
+    let start = State::start();
+    let mut parent = ParentMap::new();
+    let mut queue = BinaryHeap::new();
+    let score = scorer.score(&start.partial(max_cover));
+    let (idx, _) = parent.insert_full(start.key_with_words(table), (start, 0));
+    queue.push((score, 0, 0, idx));
+
+    while let Some((score, path_len, total_letters, parent_state_idx)) = queue.pop() {
+        let (_, &(parent_state, _)) = parent.get_index(parent_state_idx).unwrap();
+        // the state was reached by a better path after this entry was queued
+        if parent_state.path_len != path_len || parent_state.total_letters != total_letters {
+            continue;
+        }
+        if parent_state.is_goal() {
+            budget.phase(Phase::Reconstruct);
+            let flow = on_goal(&parent, parent_state_idx, budget.expanded());
+            budget.phase(Phase::Search);
+            match flow {
+                ControlFlow::Break(()) => break,
+                ControlFlow::Continue(()) => continue,
+            }
+        }
+        if path_len >= max_words {
+            continue;
+        }
+        budget.expand(parent_state.used_chars_mask, parent.len(), queue.len())?;
+        budget.trace(
+            parent_state.last_word(table),
+            parent_state.used_chars_mask,
+            &score,
+        );
+        for child_state in parent_state.get_child_states(table) {
+            let child_score = scorer.score(&child_state.partial(max_cover));
+            let child_state_idx = match parent.entry(child_state.key_with_words(table)) {
+                Entry::Occupied(mut entry) => {
+                    let (known, _) = entry.get();
+                    if scorer.score(&known.partial(max_cover)) >= child_score {
+                        budget.generated(true);
+                        continue;
+                    }
+                    entry.insert((child_state, parent_state_idx));
+                    entry.index()
+                }
+                Entry::Vacant(entry) => {
+                    let idx = entry.index();
+                    entry.insert((child_state, parent_state_idx));
+                    idx
+                }
+            };
+            budget.generated(false);
+            queue.push((
+                child_score,
+                child_state.path_len,
+                child_state.total_letters,
+                child_state_idx,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Solve the letterboxed game using as few words as possible.
+///
+/// Unlike [`solve`], the A* search here uses an admissible heuristic (the number of uncovered
+/// letters divided by the most letters any single word covers), so the first solution found is
+/// guaranteed to be minimal. Ties are broken with the same score [`solve`] uses.
+pub fn solve_minimal(
+    board: &Board,
+    trie: &Trie,
+    options: &SolveOptions,
+) -> Result<Solution, SolveError> {
+    let max_words = options.max_words;
+    let mut budget = Budget::new(options);
+    let table = WordTable::new(board, trie, options);
+    budget.phase(Phase::Search);
+    let max_cover = table.max_cover();
+    // no words at all, no solution of any length exists
+    if max_cover == 0 {
+        return Err(SolveError::Unsolvable { max_words });
+    }
+
+    let start = State::start();
+    let mut parent = ParentMap::new();
     let mut queue = BinaryHeap::new();
+    let bound = start.remaining_words_bound(max_cover);
     let score = start.calculate_score();
-    let (idx, _) = parent.insert_full(start, 0);
-    queue.push((score, idx));
-
-    while !queue.is_empty() {
-        let (_, parent_state_idx) = queue.pop().unwrap();
-        let (parent_state, _) = parent.get_index(parent_state_idx).unwrap();
-        for child_state in parent_state.get_child_states(trie) {
-            let child_score = child_state.calculate_score();
+    let (idx, _) = parent.insert_full(start.key(&table), (start, 0));
+    queue.push((Reverse(bound), score, 0, idx));
+
+    while let Some((Reverse(bound), _, path_len, parent_state_idx)) = queue.pop() {
+        let (_, &(parent_state, _)) = parent.get_index(parent_state_idx).unwrap();
+        // the state was reached by a shorter path after this entry was queued
+        if parent_state.path_len != path_len || path_len >= max_words {
+            continue;
+        }
+        budget.expand(parent_state.used_chars_mask, parent.len(), queue.len())?;
+        budget.trace(
+            parent_state.last_word(&table),
+            parent_state.used_chars_mask,
+            &bound,
+        );
+        for child_state in parent_state.get_child_states(&table) {
+            let visited = visit(&mut parent, &table, child_state, parent_state_idx);
+            budget.generated(visited.is_none());
+            let Some(child_state_idx) = visited else {
+                continue;
+            };
+            // every state popped so far has a bound no larger than the cost of the best
+            // solution, so the first goal generated is optimal.
             if child_state.is_goal() {
-                let (child_state_idx, _) = parent.insert_full(child_state, parent_state_idx);
-                return Some(extract_path(parent, child_state_idx));
+                budget.phase(Phase::Reconstruct);
+                return Ok(Solution {
+                    words: extract_path(&parent, &table, child_state_idx),
+                    ruled_out: options.ruled_out(child_state.path_len),
+                });
+            }
+            let bound = child_state.path_len + child_state.remaining_words_bound(max_cover);
+            if bound > max_words {
+                continue;
             }
-            let (new_idx, _) = parent.insert_full(child_state, parent_state_idx);
-            queue.push((child_score, new_idx));
+            queue.push((
+                Reverse(bound),
+                child_state.calculate_score(),
+                child_state.path_len,
+                child_state_idx,
+            ));
         }
     }
 
-    None
+    Err(SolveError::Unsolvable { max_words })
+}
+
+/// Solve the letterboxed game using as few words as possible with a breadth-first dynamic program
+/// over (end letter, letter mask) pairs.
+///
+/// Every layer of the search plays one more word, and each pair is only kept with the fewest
+/// letters it can be reached with in its first layer, so the work done is bounded by the size of
+/// the state space regardless of the dictionary. Among the solutions with the fewest words, the
+/// one with the fewest letters is returned.
+///
+/// Keeping pairs only in their first layer loses paths that an exact number of words or a limit
+/// on letters could need, so with those constraints the first solution of [`pareto_front`] is
+/// returned instead.
+pub fn solve_dp(
+    board: &Board,
+    trie: &Trie,
+    options: &SolveOptions,
+) -> Result<Solution, SolveError> {
+    if options.exact_words.is_some() || options.max_letters.is_some() {
+        return pareto_front(board, trie, options).map(|front| front.into_iter().next().unwrap());
+    }
+    let mut budget = Budget::new(options);
+    let table = WordTable::new(board, trie, options);
+    budget.phase(Phase::Search);
+    let mut reached = 0;
+    // every word sets the slots of the missing required words, so past the root only the
+    // letters and the slots of the required words tell pairs apart
+    let mask_bits = BOARD_LEN + options.must_include.len();
+    let unused_slots = (!((1u32 << mask_bits) - 1)) as u16;
+    let index = |end: usize, mask: u16| end << mask_bits | (mask & !unused_slots) as usize;
+    let pair_mask = |i: usize| (i & ((1 << mask_bits) - 1)) as u16 | unused_slots;
+    // number of distinct (end letter, state mask) pairs a state can be in
+    let state_space_len = BOARD_LEN << mask_bits;
+
+    // for every pair: the layer it was first reached in, the fewest letters used to reach it,
+    // and the (previous pair, word) it was reached with. The root has no pair.
+    let mut depth = vec![usize::MAX; state_space_len];
+    let mut letters = vec![usize::MAX; state_space_len];
+    let mut parent: Vec<(Option<usize>, usize)> = vec![(None, 0); state_space_len];
+
+    let mut layer: Vec<Option<usize>> = vec![None];
+    for words in 1..=options.max_words {
+        let mut next_layer = vec![];
+        for &from in &layer {
+            let (candidates, mask, used) = match from {
+                None => (table.next_words(Location::Root), 0, 0),
+                Some(i) => (
+                    table.next_words(Location::Idx(i >> mask_bits)),
+                    pair_mask(i),
+                    letters[i],
+                ),
+            };
+            budget.expand(mask, reached, layer.len())?;
+            for id in candidates {
+                let to = index(table.ends[id], mask | table.masks[id]);
+                let to_letters = used + table.words[id].chars().count();
+                if depth[to] == usize::MAX {
+                    depth[to] = words;
+                    reached += 1;
+                    next_layer.push(Some(to));
+                } else if depth[to] != words || letters[to] <= to_letters {
+                    budget.generated(true);
+                    continue;
+                }
+                budget.generated(false);
+                letters[to] = to_letters;
+                parent[to] = (from, id);
+            }
+        }
+
+        let goal = (0..BOARD_LEN)
+            .map(|end| index(end, GOAL_MASK))
+            .filter(|&i| depth[i] == words)
+            .min_by_key(|&i| letters[i]);
+        if let Some(mut cursor) = goal {
+            budget.phase(Phase::Reconstruct);
+            let mut path = vec![];
+            loop {
+                let (from, id) = parent[cursor];
+                path.push(table.words[id].clone());
+                match from {
+                    Some(i) => cursor = i,
+                    None => break,
+                }
+            }
+            path.reverse();
+            return Ok(Solution {
+                words: path,
+                ruled_out: words - 1,
+            });
+        }
+        layer = next_layer;
+    }
+
+    Err(SolveError::Unsolvable {
+        max_words: options.max_words,
+    })
 }
 
 /// extract the full solution from a given game state
-fn extract_path(parent: IndexMap<State, usize>, new_idx: usize) -> Vec<String> {
-    let (mut cursor_state, mut next_state) = parent.get_index(new_idx).unwrap();
+fn extract_path(parent: &ParentMap, table: &WordTable, new_idx: usize) -> Vec<String> {
     let mut path = vec![];
-    while *next_state != 0 {
-        path.push(cursor_state.word.clone());
-        (cursor_state, next_state) = parent.get_index(*next_state).unwrap();
+    let mut cursor = new_idx;
+    while let (_, &(State { word: Some(id), .. }, next)) = parent.get_index(cursor).unwrap() {
+        path.push(table.words[id].clone());
+        cursor = next;
     }
-    path.push(cursor_state.word.clone());
     path.reverse();
     path
 }
 
+/// Enumerate every solution to the board that uses at most `max_words` words.
+///
+/// Solutions are produced lazily in depth-first order. A sequence ends as soon as every letter is
+/// covered, so a solution is never extended with extra words, and each sequence is yielded once.
+pub fn solve_all(board: &Board, trie: &Trie, max_words: usize) -> Solutions {
+    let table = WordTable::new(board, trie, &SolveOptions::default());
+    let stack = if max_words == 0 {
+        vec![]
+    } else {
+        vec![(State::start(), 0)]
+    };
+    Solutions {
+        table,
+        max_words,
+        stack,
+    }
+}
+
+/// Iterator over all solutions up to a word limit, created by [`solve_all`].
+pub struct Solutions {
+    table: WordTable,
+    max_words: usize,
+    // state at each depth, and the position of the next candidate word to try from it
+    stack: Vec<(State, usize)>,
+}
+
+impl Iterator for Solutions {
+    type Item = Vec<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (state, next_candidate) = self.stack.last_mut()?;
+            let candidates = match state.location {
+                Location::Root => &self.table.first_words,
+                Location::Idx(i) => &self.table.starting_with[i],
+            };
+            let Some(&id) = candidates.get(*next_candidate) else {
+                self.stack.pop();
+                continue;
+            };
+            *next_candidate += 1;
+            let child = state.play(&self.table, id);
+
+            if child.is_goal() {
+                let path = self.stack[1..]
+                    .iter()
+                    .chain(std::iter::once(&(child, 0)))
+                    .map(|(s, _)| self.table.words[s.word.unwrap()].clone())
+                    .collect();
+                return Some(path);
+            }
+            if child.path_len < self.max_words {
+                self.stack.push((child, 0));
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::trie::{read_words, DEFAULT_WORDS};
     #[test]
     fn solve_game() {
         let board = Board::from("vkspyielurao".chars());
@@ -149,7 +744,253 @@ mod test {
         ];
         let trie = Trie::new_with_board(word_list, &board);
 
-        let solution = solve(&board, &trie);
-        assert_eq!(solution, Some(vec!["previously".into(), "yak".into()]));
+        let solution = solve(&board, &trie, &SolveOptions::default());
+        assert_eq!(
+            solution.map(|s| s.words),
+            Ok(vec!["previously".into(), "yak".into()])
+        );
+    }
+
+    #[test]
+    fn solve_limits() {
+        let board = Board::from("vkspyielurao".chars());
+        let trie = Trie::new_with_board(["kayo", "previously", "surly", "yolk"], &board);
+        let options = SolveOptions {
+            max_words: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            solve(&board, &trie, &options),
+            Err(SolveError::Unsolvable { max_words: 2 })
+        );
+
+        let options = SolveOptions {
+            max_expanded: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            solve(&board, &trie, &options),
+            Err(SolveError::LimitReached(Limit::Expanded))
+        );
+
+        let options = SolveOptions {
+            max_parent_size: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            solve_minimal(&board, &trie, &options),
+            Err(SolveError::LimitReached(Limit::ParentSize))
+        );
+
+        let options = SolveOptions {
+            deadline: Some(std::time::Instant::now()),
+            ..Default::default()
+        };
+        assert_eq!(
+            solve_dp(&board, &trie, &options),
+            Err(SolveError::LimitReached(Limit::Deadline))
+        );
+
+        let cancelled = CancelToken::new();
+        cancelled.cancel();
+        let options = SolveOptions {
+            cancel: Some(cancelled),
+            ..Default::default()
+        };
+        assert_eq!(solve(&board, &trie, &options), Err(SolveError::Cancelled));
+    }
+
+    #[test]
+    fn solve_anytime_improves() {
+        let board = Board::from("taiconvmhrle".chars());
+        let trie = Trie::new_with_board(read_words(DEFAULT_WORDS), &board);
+        let options = SolveOptions::default();
+        let mut improvements = vec![];
+        let best = solve_anytime(&board, &trie, &options, &Coverage, |i| {
+            improvements.push(i.clone())
+        })
+        .unwrap();
+
+        assert!(!improvements.is_empty());
+        assert_eq!(improvements.last().unwrap().solution, best);
+        for pair in improvements.windows(2) {
+            assert!(pair[0].solution.total_letters() > pair[1].solution.total_letters());
+            assert!(pair[0].expanded <= pair[1].expanded);
+        }
+        // the coverage ratio of a complete solution only depends on its length
+        let fewest = solve_with(&board, &trie, &options, &FewestLetters).unwrap();
+        assert_eq!(best.total_letters(), fewest.total_letters());
+
+        // "adgj", "jbeh" reach the same state as "adgjadgjbeh" with fewer letters, but only the
+        // longer word leaves room to finish within two words
+        let board = Board::from("abc def ghi jkl".chars());
+        let trie = Trie::new_with_board(["adgj", "jbeh", "hkcfil", "adgjadgjbeh"], &board);
+        let options = SolveOptions {
+            max_words: 2,
+            ..Default::default()
+        };
+        let best = solve_anytime(&board, &trie, &options, &FewestLetters, |_| {}).unwrap();
+        assert_eq!(best.words, vec!["adgjadgjbeh", "hkcfil"]);
+    }
+
+    #[test]
+    fn progress_and_cancel() {
+        let board = Board::from("degluysftnwr".chars());
+        let trie = Trie::new_with_board(read_words(DEFAULT_WORDS), &board);
+        let token = CancelToken::new();
+        let reports = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let options = SolveOptions {
+            cancel: Some(token.clone()),
+            progress: Some({
+                let reports = reports.clone();
+                std::sync::Arc::new(move |p: &Progress| {
+                    reports.lock().unwrap().push(*p);
+                    if p.expanded >= 3 {
+                        token.cancel();
+                    }
+                })
+            }),
+            progress_interval: 1,
+            ..Default::default()
+        };
+        assert_eq!(solve(&board, &trie, &options), Err(SolveError::Cancelled));
+        let reports = reports.lock().unwrap();
+        assert_eq!(
+            reports.iter().map(|p| p.expanded).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert!(reports[1].queue_len > 0);
+        assert!(reports[2].best_coverage >= reports[1].best_coverage);
+    }
+
+    #[test]
+    fn stats_and_trace() {
+        let board = Board::from("vkspyielurao".chars());
+        let trie = Trie::new_with_board(["kayo", "previously", "surly", "yak", "yolk"], &board);
+        let stats = std::sync::Arc::new(std::sync::Mutex::new(SearchStats::default()));
+        let trace = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let options = SolveOptions {
+            stats: Some(stats.clone()),
+            trace: Some(trace.clone()),
+            ..Default::default()
+        };
+        solve(&board, &trie, &options).unwrap();
+        let first = *stats.lock().unwrap();
+        assert!(first.expanded > 0);
+        assert!(first.generated >= first.expanded);
+        assert!(first.peak_parent_size > 0);
+        let trace = String::from_utf8(trace.lock().unwrap().clone()).unwrap();
+        assert_eq!(trace.lines().count(), first.expanded);
+        assert!(trace.starts_with("-\t000000000000\t"));
+
+        // a second search adds to the same statistics
+        solve_minimal(&board, &trie, &options).unwrap();
+        assert!(stats.lock().unwrap().expanded > first.expanded);
+    }
+
+    #[test]
+    fn solve_minimal_game() {
+        let board = Board::from("vkspyielurao".chars());
+        let word_list = vec![
+            "kayo",
+            "layover",
+            "poke",
+            "previously",
+            "surly",
+            "yak",
+            "yolk",
+        ];
+        let trie = Trie::new_with_board(word_list, &board);
+
+        let options = SolveOptions {
+            max_words: 5,
+            ..Default::default()
+        };
+        assert_eq!(
+            solve_minimal(&board, &trie, &options),
+            Ok(Solution {
+                words: vec!["previously".into(), "yak".into()],
+                ruled_out: 1,
+            })
+        );
+
+        // without yak, the only solution needs three words
+        let word_list = vec!["kayo", "layover", "poke", "previously", "surly", "yolk"];
+        let trie = Trie::new_with_board(word_list, &board);
+        let minimal = solve_minimal(&board, &trie, &options).unwrap();
+        assert_eq!(minimal.words.len(), 3);
+        assert_eq!(minimal.ruled_out, 2);
+
+        let options = SolveOptions {
+            max_words: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            solve_minimal(&board, &trie, &options),
+            Err(SolveError::Unsolvable { max_words: 2 })
+        );
+    }
+
+    #[test]
+    fn solve_dp_game() {
+        let board = Board::from("vkspyielurao".chars());
+        let word_list = vec![
+            "kayo",
+            "layover",
+            "poke",
+            "previously",
+            "surly",
+            "yak",
+            "yolk",
+        ];
+        let trie = Trie::new_with_board(word_list, &board);
+        assert_eq!(
+            solve_dp(&board, &trie, &SolveOptions::default()),
+            Ok(Solution {
+                words: vec!["previously".into(), "yak".into()],
+                ruled_out: 1,
+            })
+        );
+        let options = SolveOptions {
+            max_words: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            solve_dp(&board, &trie, &options),
+            Err(SolveError::Unsolvable { max_words: 1 })
+        );
+    }
+
+    #[test]
+    fn solve_all_solutions() {
+        let board = Board::from("vkspyielurao".chars());
+        let word_list = vec![
+            "kayo",
+            "layover",
+            "overlay",
+            "poke",
+            "previously",
+            "surly",
+            "yak",
+            "yolk",
+        ];
+        let trie = Trie::new_with_board(word_list, &board);
+
+        let mut solutions = solve_all(&board, &trie, 3).collect::<Vec<_>>();
+        solutions.sort();
+        assert_eq!(
+            solutions,
+            vec![
+                vec!["previously".to_string(), "yak".into()],
+                vec!["previously".into(), "yolk".into(), "kayo".into()],
+            ]
+        );
+
+        let solutions = solve_all(&board, &trie, 2).collect::<Vec<_>>();
+        assert_eq!(
+            solutions,
+            vec![vec!["previously".to_string(), "yak".into()]]
+        );
+        assert_eq!(solve_all(&board, &trie, 0).count(), 0);
     }
 }
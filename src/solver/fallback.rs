@@ -0,0 +1,132 @@
+use super::{
+    extract_path, visit, Budget, ParentMap, Phase, SolveError, SolveOptions, State, WordTable,
+};
+use crate::board::{Board, BOARD_LEN, FULL_MASK};
+use crate::trie::Trie;
+use std::cmp::Reverse;
+use std::fmt;
+
+/// Why a letter is left uncovered by [`best_partial`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UncoveredReason {
+    /// No word that may be played contains the letter.
+    NoWord,
+    /// Words contain the letter, but none of them can be chained with the words covering the
+    /// other letters within the word limit.
+    Unchainable,
+}
+
+impl fmt::Display for UncoveredReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UncoveredReason::NoWord => write!(f, "no word uses it"),
+            UncoveredReason::Unchainable => write!(f, "can't be chained with the other letters"),
+        }
+    }
+}
+
+/// The words covering the most letters of a board, found by [`best_partial`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestPartial {
+    pub words: Vec<String>,
+    /// every board letter the words don't cover, and why
+    pub uncovered: Vec<(char, UncoveredReason)>,
+}
+
+/// Find the sequence of at most `options.max_words` words covering the most letters of the
+/// board, for boards without a full solution. Ties are broken by fewer words and then fewer
+/// letters. If the board has a solution, a solution with the fewest words is returned.
+///
+/// This is a breadth-first search over every state reachable within the word limit, keeping the
+/// best state seen.
+pub fn best_partial(
+    board: &Board,
+    trie: &Trie,
+    options: &SolveOptions,
+) -> Result<BestPartial, SolveError> {
+    let mut budget = Budget::new(options);
+    let table = WordTable::new(board, trie, options);
+    budget.phase(Phase::Search);
+
+    let start = State::start();
+    let mut parent = ParentMap::new();
+    let (idx, _) = parent.insert_full(start.key(&table), (start, 0));
+    let rank = |s: &State| {
+        (
+            s.covered().count_ones(),
+            Reverse(s.path_len),
+            Reverse(s.total_letters),
+        )
+    };
+    let mut best = (rank(&start), idx);
+    let mut layer = vec![idx];
+    'search: for _ in 0..options.max_words {
+        let mut next_layer = vec![];
+        for &parent_state_idx in &layer {
+            let (_, &(parent_state, _)) = parent.get_index(parent_state_idx).unwrap();
+            budget.expand(parent_state.used_chars_mask, parent.len(), layer.len())?;
+            for child_state in parent_state.get_child_states(&table) {
+                let visited = visit(&mut parent, &table, child_state, parent_state_idx);
+                budget.generated(visited.is_none());
+                let Some(child_state_idx) = visited else {
+                    continue;
+                };
+                if rank(&child_state) > best.0 {
+                    best = (rank(&child_state), child_state_idx);
+                }
+                if child_state.is_goal() {
+                    break 'search;
+                }
+                next_layer.push(child_state_idx);
+            }
+        }
+        layer = next_layer;
+    }
+
+    budget.phase(Phase::Reconstruct);
+    let (_, &(state, _)) = parent.get_index(best.1).unwrap();
+    let playable = table.masks.iter().fold(0, |mask, m| mask | m) & FULL_MASK;
+    let uncovered = (0..BOARD_LEN)
+        .filter(|i| state.covered() & 1 << i == 0)
+        .map(|i| {
+            let reason = if playable & 1 << i == 0 {
+                UncoveredReason::NoWord
+            } else {
+                UncoveredReason::Unchainable
+            };
+            (board.letters[i], reason)
+        })
+        .collect();
+    Ok(BestPartial {
+        words: extract_path(&parent, &table, best.1),
+        uncovered,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn best_partial_coverage() {
+        let board = Board::from("vkspyielurao".chars());
+        let trie = Trie::new_with_board(["previously", "yak"], &board);
+        let options = SolveOptions::default();
+        let partial = best_partial(&board, &trie, &options).unwrap();
+        assert_eq!(partial.words, vec!["previously", "yak"]);
+        assert!(partial.uncovered.is_empty());
+
+        // nothing uses v, and "kayo" can't be chained with "perilously"
+        let trie = Trie::new_with_board(["perilously", "kayo"], &board);
+        let partial = best_partial(&board, &trie, &options).unwrap();
+        assert_eq!(partial.words, vec!["perilously"]);
+        assert_eq!(
+            partial.uncovered,
+            vec![
+                ('v', UncoveredReason::NoWord),
+                ('k', UncoveredReason::Unchainable),
+                ('a', UncoveredReason::Unchainable),
+            ]
+        );
+    }
+}
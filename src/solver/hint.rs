@@ -0,0 +1,281 @@
+use super::{Budget, Phase, Solution, SolveError, SolveOptions, State, StateKey, WordTable};
+use crate::board::Board;
+use crate::trie::Trie;
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A word suggested by [`hint`] to play next
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hint {
+    pub word: String,
+    /// Fewest words a solution can have when this word is played next, counting the words
+    /// already played. `None` if no solution within `max_words` starts this way.
+    pub solution_words: Option<usize>,
+    /// number of letters this word covers that weren't covered yet
+    pub new_letters: usize,
+}
+
+/// Reasons [`hint`] can fail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintError {
+    /// The word at this position of the words already played can't be played there.
+    Unplayable { position: usize },
+    /// The search for the fewest words finishing the game stopped early.
+    Solve(SolveError),
+}
+
+impl From<SolveError> for HintError {
+    fn from(error: SolveError) -> Self {
+        HintError::Solve(error)
+    }
+}
+
+impl fmt::Display for HintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HintError::Unplayable { position } => {
+                write!(f, "word {} can't be played there", position + 1)
+            }
+            HintError::Solve(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for HintError {}
+
+/// Suggest the next word to play after `played`, without giving away the whole solution.
+///
+/// Every word that can be played next is returned, ranked by the fewest words a solution
+/// continuing with it can have, and then by the number of new letters it covers. Fails with
+/// [`HintError::Unplayable`] if one of the words played so far isn't a legal move.
+pub fn hint<S: AsRef<str>>(
+    board: &Board,
+    trie: &Trie,
+    played: &[S],
+    options: &SolveOptions,
+) -> Result<Vec<Hint>, HintError> {
+    let mut budget = Budget::new(options);
+    let table = WordTable::new(board, trie, options);
+    budget.phase(Phase::Search);
+
+    let mut state = State::start();
+    for (position, word) in played.iter().enumerate() {
+        let id = table
+            .next_words(state.location)
+            .find(|&id| table.words[id] == word.as_ref())
+            .ok_or(HintError::Unplayable { position })?;
+        state = state.play(&table, id);
+    }
+    if state.is_goal() {
+        return Ok(vec![]);
+    }
+
+    let max_extra = options.max_words.saturating_sub(state.path_len);
+    let to_finish = words_to_finish(&table, state, max_extra, &mut budget)?;
+    let mut hints = vec![];
+    for child in state.get_child_states(&table) {
+        let solution_words = to_finish
+            .get(&child.key(&table))
+            .map(|n| child.path_len + n);
+        hints.push(Hint {
+            word: table.words[child.word.unwrap()].clone(),
+            solution_words,
+            new_letters: (child.covered() & !state.covered()).count_ones() as usize,
+        });
+    }
+    hints.sort_by_key(|h| {
+        (
+            h.solution_words.is_none(),
+            h.solution_words,
+            Reverse(h.new_letters),
+        )
+    });
+    Ok(hints)
+}
+
+/// Fewest words that finish the game from every state that can reach a goal within `max_extra`
+/// words of `from`, keyed on [`State::key`].
+///
+/// A breadth-first search from `from` records which states lead to each state it reaches, then
+/// the distances are spread backward from the goal states, so every candidate next word is
+/// ranked from the same search.
+fn words_to_finish(
+    table: &WordTable,
+    from: State,
+    max_extra: usize,
+    budget: &mut Budget,
+) -> Result<HashMap<StateKey, usize>, SolveError> {
+    let mut predecessors: HashMap<StateKey, Vec<StateKey>> =
+        HashMap::from([(from.key(table), vec![])]);
+    let mut goals = vec![];
+    let mut layer = vec![from];
+    for _ in 0..max_extra {
+        let mut next_layer = vec![];
+        for state in &layer {
+            budget.expand(state.used_chars_mask, predecessors.len(), layer.len())?;
+            for child in state.get_child_states(table) {
+                let entry = predecessors.entry(child.key(table));
+                let new = matches!(entry, Entry::Vacant(_));
+                budget.generated(!new);
+                entry.or_default().push(state.key(table));
+                if !new {
+                    continue;
+                }
+                if child.is_goal() {
+                    goals.push(child.key(table));
+                } else {
+                    next_layer.push(child);
+                }
+            }
+        }
+        layer = next_layer;
+    }
+
+    let mut to_finish: HashMap<_, _> = goals.iter().map(|&goal| (goal, 0)).collect();
+    let mut layer = goals;
+    let mut words = 0;
+    while !layer.is_empty() {
+        words += 1;
+        let mut next_layer = vec![];
+        for key in &layer {
+            for &previous in &predecessors[key] {
+                if let Entry::Vacant(entry) = to_finish.entry(previous) {
+                    entry.insert(words);
+                    next_layer.push(previous);
+                }
+            }
+        }
+        layer = next_layer;
+    }
+    Ok(to_finish)
+}
+
+/// One piece of a solution given away by [`clues`], from least to most revealing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Clue {
+    WordCount(usize),
+    FirstLetters(Vec<char>),
+    Lengths(Vec<usize>),
+    /// the word at this position of the solution
+    Word(usize, String),
+}
+
+impl fmt::Display for Clue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let list = |items: Vec<String>| items.join(", ");
+        match self {
+            Clue::WordCount(1) => write!(f, "The solution uses 1 word."),
+            Clue::WordCount(n) => write!(f, "The solution uses {} words.", n),
+            Clue::FirstLetters(letters) if letters.len() == 1 => {
+                write!(f, "The word starts with {}.", letters[0].to_uppercase())
+            }
+            Clue::FirstLetters(letters) => write!(
+                f,
+                "The words start with {}.",
+                list(
+                    letters
+                        .iter()
+                        .map(|c| c.to_uppercase().to_string())
+                        .collect()
+                )
+            ),
+            Clue::Lengths(lengths) if lengths.len() == 1 => {
+                write!(f, "The word has {} letters.", lengths[0])
+            }
+            Clue::Lengths(lengths) => write!(
+                f,
+                "The words have {} letters.",
+                list(lengths.iter().map(|n| n.to_string()).collect())
+            ),
+            Clue::Word(position, word) => {
+                write!(f, "Word {} is {}.", position + 1, word.to_uppercase())
+            }
+        }
+    }
+}
+
+/// The first `level` clues to `solution`, each giving away a bit more than the one before: the
+/// number of words, the first letter of each word, the length of each word, and then the words
+/// themselves one at a time. The highest level, `3 + solution.words.len()`, gives away everything.
+pub fn clues(solution: &Solution, level: usize) -> Vec<Clue> {
+    let words = &solution.words;
+    let mut clues = vec![
+        Clue::WordCount(words.len()),
+        Clue::FirstLetters(words.iter().filter_map(|w| w.chars().next()).collect()),
+        Clue::Lengths(words.iter().map(|w| w.chars().count()).collect()),
+    ];
+    clues.extend(
+        words
+            .iter()
+            .enumerate()
+            .map(|(position, word)| Clue::Word(position, word.clone())),
+    );
+    clues.truncate(level);
+    clues
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hints_rank_minimal_finish_first() {
+        let board = Board::from("vkspyielurao".chars());
+        let word_list = vec!["kayo", "layover", "previously", "surly", "yak", "yolk"];
+        let trie = Trie::new_with_board(word_list, &board);
+        let options = SolveOptions::default();
+
+        let hints = hint::<&str>(&board, &trie, &[], &options).unwrap();
+        assert_eq!(hints[0].word, "previously");
+        assert_eq!(hints[0].solution_words, Some(2));
+        assert_eq!(hints[0].new_letters, 10);
+
+        let hints = hint(&board, &trie, &["previously"], &options).unwrap();
+        let words: Vec<_> = hints.iter().map(|h| h.word.as_str()).collect();
+        assert_eq!(words, vec!["yak", "yolk"]);
+        assert_eq!(hints[1].solution_words, Some(3));
+
+        let options = SolveOptions {
+            max_words: 2,
+            ..Default::default()
+        };
+        let hints = hint(&board, &trie, &["previously"], &options).unwrap();
+        assert_eq!(hints[1].solution_words, None);
+
+        assert_eq!(
+            hint(&board, &trie, &["previously", "yak"], &options),
+            Ok(vec![])
+        );
+        assert_eq!(
+            hint(&board, &trie, &["previously", "kayo"], &options),
+            Err(HintError::Unplayable { position: 1 })
+        );
+    }
+
+    #[test]
+    fn graded_clues() {
+        let solution = Solution::from(vec!["previously".to_string(), "yak".to_string()]);
+        assert_eq!(clues(&solution, 0), vec![]);
+        assert_eq!(
+            clues(&solution, 3),
+            vec![
+                Clue::WordCount(2),
+                Clue::FirstLetters(vec!['p', 'y']),
+                Clue::Lengths(vec![10, 3]),
+            ]
+        );
+        let all = clues(&solution, 10);
+        assert_eq!(all.len(), 5);
+        assert_eq!(all[4], Clue::Word(1, "yak".into()));
+        assert_eq!(all[1].to_string(), "The words start with P, Y.");
+        assert_eq!(all[3].to_string(), "Word 1 is PREVIOUSLY.");
+
+        let solution = Solution::from(vec!["previously".to_string()]);
+        let all = clues(&solution, 3);
+        assert_eq!(all[0].to_string(), "The solution uses 1 word.");
+        assert_eq!(all[1].to_string(), "The word starts with P.");
+        assert_eq!(all[2].to_string(), "The word has 10 letters.");
+    }
+}
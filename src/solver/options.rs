@@ -0,0 +1,364 @@
+use crate::board::FULL_MASK;
+use std::collections::HashSet;
+use std::fmt;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Limits on how far and how long a search may run, and hooks to watch and stop it.
+#[derive(Clone)]
+pub struct SolveOptions {
+    /// Most words a solution may use.
+    pub max_words: usize,
+    /// Most states the search may expand.
+    pub max_expanded: Option<usize>,
+    /// Most states the search may keep track of at once.
+    pub max_parent_size: Option<usize>,
+    /// Time at which the search gives up.
+    pub deadline: Option<Instant>,
+    /// Token that can be cancelled from another thread to stop the search.
+    pub cancel: Option<CancelToken>,
+    /// Called with the progress of the search every `progress_interval` expanded states.
+    pub progress: Option<ProgressCallback>,
+    pub progress_interval: usize,
+    /// Word every solution has to start with.
+    pub start_with: Option<String>,
+    /// Words every solution has to use, at most [`super::MAX_REQUIRED_WORDS`] of them.
+    pub must_include: Vec<String>,
+    /// Words no solution may use.
+    pub exclude: HashSet<String>,
+    /// Number of words every solution has to have.
+    pub exact_words: Option<usize>,
+    /// Fewest letters a word may have.
+    pub min_word_len: Option<usize>,
+    /// Most letters a word may have.
+    pub max_word_len: Option<usize>,
+    /// Most letters a solution may have in total.
+    pub max_letters: Option<usize>,
+    /// Statistics of every search run with these options are added here.
+    pub stats: Option<Arc<Mutex<SearchStats>>>,
+    /// Searches that support tracing write a line for every state they expand here: the last
+    /// word played, the letters covered as a bitmask and the score the state was ranked with.
+    pub trace: Option<Arc<Mutex<dyn Write + Send>>>,
+}
+
+/// Callback receiving [`Progress`] reports
+pub type ProgressCallback = Arc<dyn Fn(&Progress) + Send + Sync>;
+
+impl Default for SolveOptions {
+    fn default() -> Self {
+        SolveOptions {
+            max_words: 8,
+            max_expanded: None,
+            max_parent_size: None,
+            deadline: None,
+            cancel: None,
+            progress: None,
+            progress_interval: 1000,
+            start_with: None,
+            must_include: vec![],
+            exclude: HashSet::new(),
+            exact_words: None,
+            min_word_len: None,
+            max_word_len: None,
+            max_letters: None,
+            stats: None,
+            trace: None,
+        }
+    }
+}
+
+impl SolveOptions {
+    /// Whether constraints besides the word limit narrow down the solutions, so that what a
+    /// search rules out only holds under those constraints.
+    pub fn constrained(&self) -> bool {
+        self.start_with.is_some()
+            || !self.must_include.is_empty()
+            || !self.exclude.is_empty()
+            || self.exact_words.is_some()
+            || self.min_word_len.is_some()
+            || self.max_word_len.is_some()
+            || self.max_letters.is_some()
+    }
+
+    /// [`super::Solution::ruled_out`] of the fewest words solution an exact search found. With
+    /// an exact number of words shorter solutions are never searched, so nothing is ruled out.
+    pub(super) fn ruled_out(&self, words: usize) -> usize {
+        match self.exact_words {
+            Some(_) => 0,
+            None => words - 1,
+        }
+    }
+}
+
+impl fmt::Debug for SolveOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SolveOptions")
+            .field("max_words", &self.max_words)
+            .field("max_expanded", &self.max_expanded)
+            .field("max_parent_size", &self.max_parent_size)
+            .field("deadline", &self.deadline)
+            .field("cancel", &self.cancel)
+            .field("progress", &self.progress.as_ref().map(|_| ".."))
+            .field("progress_interval", &self.progress_interval)
+            .field("start_with", &self.start_with)
+            .field("must_include", &self.must_include)
+            .field("exclude", &self.exclude)
+            .field("exact_words", &self.exact_words)
+            .field("min_word_len", &self.min_word_len)
+            .field("max_word_len", &self.max_word_len)
+            .field("max_letters", &self.max_letters)
+            .field("stats", &self.stats)
+            .field("trace", &self.trace.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+/// Shared flag used to cancel a running search. Clones refer to the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every search using this token to stop as soon as possible.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Snapshot of a running search, passed to [`SolveOptions::progress`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// states expanded so far
+    pub expanded: usize,
+    /// states waiting to be expanded
+    pub queue_len: usize,
+    /// most board letters covered by any state expanded so far
+    pub best_coverage: u32,
+    /// time since the search started
+    pub elapsed: Duration,
+}
+
+/// Counters collected while searching, see [`SolveOptions::stats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// child states produced by expanding states
+    pub generated: usize,
+    /// states expanded
+    pub expanded: usize,
+    /// generated states dropped because their state was already reached by a path at least as good
+    pub duplicates: usize,
+    /// most states tracked at once
+    pub peak_parent_size: usize,
+    /// most states waiting to be expanded at once
+    pub peak_queue_len: usize,
+    /// time spent building the word table before searching
+    pub setup_time: Duration,
+    pub search_time: Duration,
+    /// time spent turning the states found back into words
+    pub reconstruct_time: Duration,
+}
+
+impl SearchStats {
+    /// add the statistics of another search to these
+    fn merge(&mut self, other: &SearchStats) {
+        self.generated += other.generated;
+        self.expanded += other.expanded;
+        self.duplicates += other.duplicates;
+        self.peak_parent_size = self.peak_parent_size.max(other.peak_parent_size);
+        self.peak_queue_len = self.peak_queue_len.max(other.peak_queue_len);
+        self.setup_time += other.setup_time;
+        self.search_time += other.search_time;
+        self.reconstruct_time += other.reconstruct_time;
+    }
+}
+
+/// Phases of a search timed in [`SearchStats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Phase {
+    Setup,
+    Search,
+    Reconstruct,
+}
+
+/// The limit in [`SolveOptions`] that stopped a search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    Expanded,
+    ParentSize,
+    Deadline,
+}
+
+/// Reasons a search can end without a solution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    /// The search was exhaustive: no solution with `max_words` words or fewer exists.
+    Unsolvable { max_words: usize },
+    /// The strategy gave up without proving that no solution exists.
+    NotFound,
+    /// A limit was hit before the search finished.
+    LimitReached(Limit),
+    /// The search was cancelled through [`SolveOptions::cancel`].
+    Cancelled,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Unsolvable { max_words: 1 } => write!(f, "no 1-word solution exists"),
+            SolveError::Unsolvable { max_words } => {
+                write!(f, "no solution with {} words or fewer exists", max_words)
+            }
+            SolveError::NotFound => write!(f, "the search gave up without trying every path"),
+            SolveError::LimitReached(Limit::Expanded) => {
+                write!(f, "gave up after expanding too many states")
+            }
+            SolveError::LimitReached(Limit::ParentSize) => {
+                write!(f, "gave up after tracking too many states")
+            }
+            SolveError::LimitReached(Limit::Deadline) => write!(f, "ran out of time"),
+            SolveError::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Counts the work done by a search, checks it against the limits of its options and reports
+/// progress. The statistics are handed to [`SolveOptions::stats`] when the budget is dropped.
+pub(super) struct Budget<'o> {
+    options: &'o SolveOptions,
+    started: Instant,
+    best_coverage: u32,
+    stats: SearchStats,
+    /// whether this budget times the phases of the search
+    timed: bool,
+    phase: Phase,
+    phase_started: Instant,
+}
+
+impl<'o> Budget<'o> {
+    /// Start counting, in the setup phase.
+    pub(super) fn new(options: &'o SolveOptions) -> Self {
+        let now = Instant::now();
+        Budget {
+            options,
+            started: now,
+            best_coverage: 0,
+            stats: SearchStats::default(),
+            timed: true,
+            phase: Phase::Setup,
+            phase_started: now,
+        }
+    }
+
+    /// Budget for one thread of a search that is timed by another budget. Only counts.
+    pub(super) fn untimed(options: &'o SolveOptions) -> Self {
+        Budget {
+            timed: false,
+            ..Budget::new(options)
+        }
+    }
+
+    pub(super) fn options(&self) -> &'o SolveOptions {
+        self.options
+    }
+
+    /// number of states expanded so far
+    pub(super) fn expanded(&self) -> usize {
+        self.stats.expanded
+    }
+
+    /// Switch the phase time is counted towards.
+    pub(super) fn phase(&mut self, phase: Phase) {
+        if !self.timed {
+            return;
+        }
+        let now = Instant::now();
+        let elapsed = now - self.phase_started;
+        match self.phase {
+            Phase::Setup => self.stats.setup_time += elapsed,
+            Phase::Search => self.stats.search_time += elapsed,
+            Phase::Reconstruct => self.stats.reconstruct_time += elapsed,
+        }
+        self.phase = phase;
+        self.phase_started = now;
+    }
+
+    /// Record a generated child state, and whether it was dropped as a duplicate.
+    pub(super) fn generated(&mut self, duplicate: bool) {
+        self.stats.generated += 1;
+        self.stats.duplicates += duplicate as usize;
+    }
+
+    /// Write a line for an expanded state to [`SolveOptions::trace`].
+    pub(super) fn trace(&self, word: Option<&str>, covered: u16, score: &dyn fmt::Display) {
+        if let Some(trace) = &self.options.trace {
+            let mut trace = trace.lock().unwrap();
+            // tracing is best effort, a failing writer shouldn't stop the search
+            let _ = writeln!(
+                trace,
+                "{}\t{:012b}\t{}",
+                word.unwrap_or("-"),
+                covered & FULL_MASK,
+                score
+            );
+        }
+    }
+
+    /// Record the expansion of a state covering the letters in `covered`, while `tracked` states
+    /// are kept by the search and `queued` of them are waiting to be expanded.
+    pub(super) fn expand(
+        &mut self,
+        covered: u16,
+        tracked: usize,
+        queued: usize,
+    ) -> Result<(), SolveError> {
+        self.stats.expanded += 1;
+        self.stats.peak_parent_size = self.stats.peak_parent_size.max(tracked);
+        self.stats.peak_queue_len = self.stats.peak_queue_len.max(queued);
+        self.best_coverage = self.best_coverage.max((covered & FULL_MASK).count_ones());
+        let options = self.options;
+        let expanded = self.stats.expanded;
+        if let Some(progress) = &options.progress {
+            if expanded % options.progress_interval.max(1) == 0 {
+                progress(&Progress {
+                    expanded,
+                    queue_len: queued,
+                    best_coverage: self.best_coverage,
+                    elapsed: self.started.elapsed(),
+                });
+            }
+        }
+        if options.cancel.as_ref().is_some_and(|c| c.is_cancelled()) {
+            return Err(SolveError::Cancelled);
+        }
+        if options.max_expanded.is_some_and(|max| expanded > max) {
+            return Err(SolveError::LimitReached(Limit::Expanded));
+        }
+        if options.max_parent_size.is_some_and(|max| tracked > max) {
+            return Err(SolveError::LimitReached(Limit::ParentSize));
+        }
+        if options.deadline.is_some_and(|d| Instant::now() >= d) {
+            return Err(SolveError::LimitReached(Limit::Deadline));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Budget<'_> {
+    fn drop(&mut self) {
+        self.phase(self.phase);
+        if let Some(stats) = &self.options.stats {
+            stats.lock().unwrap().merge(&self.stats);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use super::{
+    Budget, Phase, Solution, SolveError, SolveOptions, Solver, State, StateKey, WordTable,
+};
+use crate::board::Board;
+use crate::trie::Trie;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Exact search split across threads by first word.
+///
+/// Each thread takes the next unexplored first word and runs a depth-first search from it,
+/// bounded by the fewest words any thread has found a solution with so far. Every thread keeps
+/// its own table of states proven to have no short enough finish. The result always uses the
+/// fewest words, but which of several minimal solutions is returned depends on thread timing.
+///
+/// `max_expanded` and `max_parent_size` limits apply to each thread separately.
+#[derive(Debug, Clone, Copy)]
+pub struct Parallel {
+    pub threads: usize,
+}
+
+impl Default for Parallel {
+    fn default() -> Self {
+        Parallel {
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+}
+
+/// State shared between the threads of a [`Parallel`] search
+struct Shared {
+    /// fewest words of any solution found so far, `max_words + 1` before the first one
+    best_words: AtomicUsize,
+    best: Mutex<Option<Vec<usize>>>,
+    /// next first word to hand out
+    next_first: AtomicUsize,
+    /// set when a thread hits a limit, so the others stop too
+    stop: AtomicBool,
+}
+
+impl Shared {
+    /// most words a solution may still use to improve on the best one
+    fn limit(&self) -> usize {
+        self.best_words.load(Ordering::Relaxed) - 1
+    }
+
+    fn offer(&self, path: &[usize]) {
+        let mut best = self.best.lock().unwrap();
+        if path.len() < self.best_words.load(Ordering::Relaxed) {
+            self.best_words.store(path.len(), Ordering::Relaxed);
+            *best = Some(path.to_vec());
+        }
+    }
+}
+
+/// The depth-first search run by one thread
+struct Worker<'a> {
+    table: &'a WordTable,
+    shared: &'a Shared,
+    budget: Budget<'a>,
+    max_cover: usize,
+    /// the most extra words each state is known to have no finish within
+    failed: HashMap<StateKey, usize>,
+}
+
+impl Worker<'_> {
+    fn run(&mut self) -> Result<(), SolveError> {
+        loop {
+            let next = self.shared.next_first.fetch_add(1, Ordering::Relaxed);
+            let Some(&first) = self.table.first_words.get(next) else {
+                return Ok(());
+            };
+            if self.shared.stop.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            let state = State::start().play(self.table, first);
+            if !self.table.allows(&state) {
+                continue;
+            }
+            self.search(state, &mut vec![first])?;
+        }
+    }
+
+    fn search(&mut self, state: State, path: &mut Vec<usize>) -> Result<(), SolveError> {
+        if state.is_goal() {
+            self.shared.offer(path);
+            return Ok(());
+        }
+        let limit = self.shared.limit();
+        if state.path_len + state.remaining_words_bound(self.max_cover) > limit {
+            return Ok(());
+        }
+        let remaining = limit - state.path_len;
+        if self
+            .failed
+            .get(&state.key(self.table))
+            .is_some_and(|&known| known >= remaining)
+        {
+            return Ok(());
+        }
+        if self.shared.stop.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        self.budget
+            .expand(state.used_chars_mask, self.failed.len(), 0)?;
+
+        for child in state.get_child_states(self.table) {
+            self.budget.generated(false);
+            path.push(child.word.unwrap());
+            self.search(child, path)?;
+            path.pop();
+        }
+        // every child was searched up to at least the current limit, so nothing shorter than
+        // the best solution finishes from here
+        let remaining = self.shared.limit().saturating_sub(state.path_len);
+        let known = self.failed.entry(state.key(self.table)).or_insert(0);
+        *known = remaining.max(*known);
+        Ok(())
+    }
+}
+
+impl Solver for Parallel {
+    fn name(&self) -> &'static str {
+        "parallel"
+    }
+
+    fn exact(&self) -> bool {
+        true
+    }
+
+    fn solve(
+        &self,
+        board: &Board,
+        trie: &Trie,
+        options: &SolveOptions,
+    ) -> Result<Solution, SolveError> {
+        let mut budget = Budget::new(options);
+        let table = WordTable::new(board, trie, options);
+        budget.phase(Phase::Search);
+        let max_cover = table.max_cover();
+        let shared = Shared {
+            best_words: AtomicUsize::new(options.max_words + 1),
+            best: Mutex::new(None),
+            next_first: AtomicUsize::new(0),
+            stop: AtomicBool::new(false),
+        };
+
+        let results = thread::scope(|scope| {
+            let handles = (0..self.threads.max(1))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut worker = Worker {
+                            table: &table,
+                            shared: &shared,
+                            budget: Budget::untimed(options),
+                            max_cover,
+                            failed: HashMap::new(),
+                        };
+                        let result = worker.run();
+                        if result.is_err() {
+                            shared.stop.store(true, Ordering::Relaxed);
+                        }
+                        result
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        results.into_iter().collect::<Result<(), _>>()?;
+        budget.phase(Phase::Reconstruct);
+
+        match shared.best.into_inner().unwrap() {
+            Some(path) => Ok(Solution {
+                ruled_out: options.ruled_out(path.len()),
+                words: path.into_iter().map(|id| table.words[id].clone()).collect(),
+            }),
+            None => Err(SolveError::Unsolvable {
+                max_words: options.max_words,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parallel_limits() {
+        let board = Board::from("vkspyielurao".chars());
+        let trie = Trie::new_with_board(["kayo", "previously", "surly", "yolk"], &board);
+        let options = SolveOptions {
+            max_words: 2,
+            ..Default::default()
+        };
+        let solver = Parallel { threads: 2 };
+        assert_eq!(
+            solver.solve(&board, &trie, &options),
+            Err(SolveError::Unsolvable { max_words: 2 })
+        );
+        let cancelled = crate::solver::CancelToken::new();
+        cancelled.cancel();
+        let options = SolveOptions {
+            cancel: Some(cancelled),
+            ..Default::default()
+        };
+        assert_eq!(
+            solver.solve(&board, &trie, &options),
+            Err(SolveError::Cancelled)
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use super::GOAL_MASK;
+use super::{Budget, Location, Phase, Solution, SolveError, SolveOptions, WordTable};
+use crate::board::{Board, BOARD_LEN};
+use crate::trie::Trie;
+use std::collections::HashMap;
+
+/// Pairs reached with a given number of words, mapped to the fewest letters they are reached with
+/// and the (pair in the previous layer, word) reaching them with that many letters.
+type Layer = HashMap<usize, (usize, Option<usize>, usize)>;
+
+/// Find every solution on the Pareto front of (words, total letters): for each number of words,
+/// the solution with the fewest letters, as long as no solution with fewer words uses as few
+/// letters. Solutions are ordered by the number of words.
+///
+/// This runs a dynamic program over (end letter, letter mask) pairs like
+/// [`super::solve_dp`], but keeps a separate layer for every number of words so that longer
+/// solutions typing fewer letters aren't lost.
+pub fn pareto_front(
+    board: &Board,
+    trie: &Trie,
+    options: &SolveOptions,
+) -> Result<Vec<Solution>, SolveError> {
+    let mut budget = Budget::new(options);
+    let table = WordTable::new(board, trie, options);
+    budget.phase(Phase::Search);
+    let index = |end: usize, mask: u16| end << u16::BITS | mask as usize;
+
+    // layers[w] holds the pairs reachable with w + 1 words
+    let mut layers: Vec<Layer> = vec![];
+    // (layer, letters, goal pair) of every solution on the front
+    let mut front: Vec<(usize, usize, usize)> = vec![];
+    let mut reached = 0;
+
+    // no solution can be longer than an exact number of words, so layers past it aren't built
+    let max_words = options
+        .exact_words
+        .map_or(options.max_words, |n| n.min(options.max_words));
+    for words in 1..=max_words {
+        let mut layer = Layer::new();
+        let sources: Vec<(Option<usize>, usize)> = match layers.last() {
+            None => vec![(None, 0)],
+            Some(prev) => prev
+                .iter()
+                // the game is over once every letter is covered
+                .filter(|(&i, _)| i as u16 != GOAL_MASK)
+                .map(|(&i, &(letters, _, _))| (Some(i), letters))
+                .collect(),
+        };
+        let queued = sources.len();
+        for (from, used) in sources {
+            let (location, mask) = match from {
+                None => (Location::Root, 0),
+                Some(i) => (Location::Idx(i >> u16::BITS), i as u16),
+            };
+            budget.expand(mask, reached, queued)?;
+            for id in table.next_words(location) {
+                let to = index(table.ends[id], mask | table.masks[id]);
+                let letters = used + table.words[id].chars().count();
+                if options.max_letters.is_some_and(|max| letters > max) {
+                    budget.generated(true);
+                    continue;
+                }
+                let entry = layer.entry(to).or_insert_with(|| {
+                    reached += 1;
+                    (usize::MAX, None, 0)
+                });
+                budget.generated(letters >= entry.0);
+                if letters < entry.0 {
+                    *entry = (letters, from, id);
+                }
+            }
+        }
+
+        let goal = (0..BOARD_LEN)
+            .filter_map(|end| {
+                let i = index(end, GOAL_MASK);
+                layer.get(&i).map(|&(letters, _, _)| (letters, i))
+            })
+            .min();
+        layers.push(layer);
+        // the game ends once every letter is covered, so shorter goals can't be extended
+        let goal = goal.filter(|_| options.exact_words.is_none_or(|n| n == words));
+        if let Some((letters, i)) = goal {
+            if front.last().is_none_or(|&(_, best, _)| letters < best) {
+                front.push((words - 1, letters, i));
+            }
+        }
+        if layers.last().unwrap().is_empty() {
+            break;
+        }
+    }
+
+    let Some(&(last_layer, _, _)) = front.first() else {
+        return Err(SolveError::Unsolvable {
+            max_words: options.max_words,
+        });
+    };
+    let ruled_out = options.ruled_out(last_layer + 1);
+    budget.phase(Phase::Reconstruct);
+    let solutions = front
+        .into_iter()
+        .map(|(last_layer, _, goal)| {
+            let mut path = vec![];
+            let mut cursor = Some(goal);
+            for layer in layers[..=last_layer].iter().rev() {
+                let (_, from, id) = layer[&cursor.unwrap()];
+                path.push(table.words[id].clone());
+                cursor = from;
+            }
+            path.reverse();
+            Solution {
+                words: path,
+                ruled_out,
+            }
+        })
+        .collect();
+    Ok(solutions)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pareto_tradeoff() {
+        let board = Board::from("abc def ghi jkl".chars());
+        let word_list = vec!["adgj", "jbeh", "hkcfil", "adgjadgjbeh", "adgjadgjadgjbeh"];
+        let trie = Trie::new_with_board(word_list, &board);
+        let front = pareto_front(&board, &trie, &SolveOptions::default()).unwrap();
+        assert_eq!(
+            front,
+            vec![
+                Solution {
+                    words: vec!["adgjadgjbeh".into(), "hkcfil".into()],
+                    ruled_out: 1,
+                },
+                Solution {
+                    words: vec!["adgj".into(), "jbeh".into(), "hkcfil".into()],
+                    ruled_out: 1,
+                },
+            ]
+        );
+
+        let options = SolveOptions {
+            max_words: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            pareto_front(&board, &trie, &options),
+            Err(SolveError::Unsolvable { max_words: 1 })
+        );
+    }
+}
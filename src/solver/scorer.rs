@@ -0,0 +1,173 @@
+use crate::board::FULL_MASK;
+use crate::non_nan::OrderedF32;
+use std::fmt;
+
+/// What a [`Scorer`] gets to see of a partial solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partial {
+    /// words played so far
+    pub words: usize,
+    /// letters typed so far, counting junction letters twice
+    pub letters: usize,
+    /// mask of the board letters covered so far
+    pub covered: u16,
+    /// the most board letters any single playable word covers
+    pub max_cover: usize,
+}
+
+impl Partial {
+    /// number of board letters not covered yet
+    pub fn uncovered(&self) -> usize {
+        (FULL_MASK & !self.covered).count_ones() as usize
+    }
+
+    /// Letters typed more than once, not counting the junction letters shared between words.
+    pub fn repeats(&self) -> usize {
+        let chain_len = self.letters - self.words.saturating_sub(1);
+        chain_len - self.covered.count_ones() as usize
+    }
+}
+
+/// Priority of a partial solution, higher is better. Compared component by component.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Score(Vec<OrderedF32>);
+
+impl Score {
+    pub fn new(value: f32) -> Self {
+        Score(vec![OrderedF32(value)])
+    }
+
+    /// break ties in this score with `next`
+    pub fn then(mut self, next: Score) -> Self {
+        self.0.extend(next.0);
+        self
+    }
+}
+
+/// components separated by commas, eg: "0.75,-3"
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, OrderedF32(value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        Ok(())
+    }
+}
+
+/// An objective for the A* search. The search expands partial solutions with the highest score
+/// first, and among paths reaching the same state with the same number of words keeps the one
+/// with the highest score.
+///
+/// When the score of a partial solution never overestimates the score of any solution extending
+/// it, and paths reaching the same state with as many words score in the order of their best
+/// solutions, the first solution found is the best one for the objective within the word
+/// limit.
+pub trait Scorer: Send + Sync {
+    fn score(&self, partial: &Partial) -> Score;
+}
+
+/// ratio of covered letters to letters typed, +1 in the denominator avoids division by zero
+pub(super) fn coverage_ratio(covered: u16, letters: usize) -> f32 {
+    covered.count_ones() as f32 / (1 + letters) as f32
+}
+
+/// Prefer covering the most letters with the fewest letters typed. Finds solutions quickly, but
+/// doesn't guarantee they are the best for any objective.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Coverage;
+
+impl Scorer for Coverage {
+    fn score(&self, partial: &Partial) -> Score {
+        Score::new(coverage_ratio(partial.covered, partial.letters))
+    }
+}
+
+/// Use as few words as possible.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FewestWords;
+
+impl Scorer for FewestWords {
+    fn score(&self, partial: &Partial) -> Score {
+        let remaining = partial.uncovered().div_ceil(partial.max_cover.max(1));
+        Score::new(-((partial.words + remaining) as f32))
+    }
+}
+
+/// Type as few letters as possible.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FewestLetters;
+
+impl Scorer for FewestLetters {
+    fn score(&self, partial: &Partial) -> Score {
+        // every uncovered letter has to be typed at least once more
+        Score::new(-((partial.letters + partial.uncovered()) as f32))
+    }
+}
+
+/// Type as few letters more than once as possible, see [`Partial::repeats`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FewestRepeats;
+
+impl Scorer for FewestRepeats {
+    fn score(&self, partial: &Partial) -> Score {
+        Score::new(-(partial.repeats() as f32))
+    }
+}
+
+/// Optimize the first objective, breaking ties with the following ones in order.
+#[derive(Default)]
+pub struct Lexicographic(pub Vec<Box<dyn Scorer>>);
+
+impl Scorer for Lexicographic {
+    fn score(&self, partial: &Partial) -> Score {
+        Score(
+            self.0
+                .iter()
+                .flat_map(|scorer| scorer.score(partial).0)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn partial_repeats() {
+        // SENTRY, YES on the readme board: S, E and Y are typed twice, Y is shared
+        let partial = Partial {
+            words: 2,
+            letters: 9,
+            covered: 0b111111,
+            max_cover: 6,
+        };
+        assert_eq!(partial.uncovered(), 6);
+        assert_eq!(partial.repeats(), 2);
+    }
+
+    #[test]
+    fn lexicographic_scores() {
+        let scorer = Lexicographic(vec![Box::new(FewestWords), Box::new(FewestLetters)]);
+        let short = Partial {
+            words: 2,
+            letters: 10,
+            covered: FULL_MASK,
+            max_cover: 8,
+        };
+        let long = Partial {
+            letters: 14,
+            ..short
+        };
+        let more_words = Partial {
+            words: 3,
+            letters: 9,
+            ..short
+        };
+        assert!(scorer.score(&short) > scorer.score(&long));
+        assert!(scorer.score(&long) > scorer.score(&more_words));
+    }
+}
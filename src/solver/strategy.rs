@@ -0,0 +1,457 @@
+use super::{
+    extract_path, solve_dp, solve_minimal, solve_with, Budget, Coverage, ParentMap, Phase, Scorer,
+    Solution, SolveError, SolveOptions, State, WordTable,
+};
+use crate::board::Board;
+use crate::trie::Trie;
+use indexmap::map::Entry;
+use std::cmp::Reverse;
+
+/// A strategy for solving a board.
+pub trait Solver {
+    /// short name of the strategy, used in reports
+    fn name(&self) -> &'static str;
+
+    /// whether the strategy always finds a solution with the fewest words when one exists
+    fn exact(&self) -> bool;
+
+    fn solve(
+        &self,
+        board: &Board,
+        trie: &Trie,
+        options: &SolveOptions,
+    ) -> Result<Solution, SolveError>;
+}
+
+/// The A* search of [`solve_with`], by default guided by the ratio of covered letters to letters
+/// typed like [`super::solve`].
+pub struct AStar {
+    pub scorer: Box<dyn Scorer>,
+}
+
+impl Default for AStar {
+    fn default() -> Self {
+        AStar {
+            scorer: Box::new(Coverage),
+        }
+    }
+}
+
+impl Solver for AStar {
+    fn name(&self) -> &'static str {
+        "astar"
+    }
+
+    fn exact(&self) -> bool {
+        false
+    }
+
+    fn solve(
+        &self,
+        board: &Board,
+        trie: &Trie,
+        options: &SolveOptions,
+    ) -> Result<Solution, SolveError> {
+        solve_with(board, trie, options, self.scorer.as_ref())
+    }
+}
+
+/// The A* search of [`solve_minimal`] with an admissible bound on the words left.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinimalAStar;
+
+impl Solver for MinimalAStar {
+    fn name(&self) -> &'static str {
+        "exact"
+    }
+
+    fn exact(&self) -> bool {
+        true
+    }
+
+    fn solve(
+        &self,
+        board: &Board,
+        trie: &Trie,
+        options: &SolveOptions,
+    ) -> Result<Solution, SolveError> {
+        solve_minimal(board, trie, options)
+    }
+}
+
+/// The breadth-first dynamic program of [`solve_dp`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BreadthFirst;
+
+impl Solver for BreadthFirst {
+    fn name(&self) -> &'static str {
+        "dp"
+    }
+
+    fn exact(&self) -> bool {
+        true
+    }
+
+    fn solve(
+        &self,
+        board: &Board,
+        trie: &Trie,
+        options: &SolveOptions,
+    ) -> Result<Solution, SolveError> {
+        solve_dp(board, trie, options)
+    }
+}
+
+/// Always play the word covering the most new letters (the shortest one on ties), without ever
+/// backtracking. Fast, but may miss solutions entirely.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Greedy;
+
+impl Solver for Greedy {
+    fn name(&self) -> &'static str {
+        "greedy"
+    }
+
+    fn exact(&self) -> bool {
+        false
+    }
+
+    fn solve(
+        &self,
+        board: &Board,
+        trie: &Trie,
+        options: &SolveOptions,
+    ) -> Result<Solution, SolveError> {
+        let mut budget = Budget::new(options);
+        let table = WordTable::new(board, trie, options);
+        budget.phase(Phase::Search);
+        let mut state = State::start();
+        let mut path = vec![];
+        while !state.is_goal() {
+            if state.path_len >= options.max_words {
+                return Err(SolveError::NotFound);
+            }
+            budget.expand(state.used_chars_mask, path.len(), 0)?;
+            let covered = state.used_chars_mask;
+            state = state
+                .get_child_states(&table)
+                .inspect(|_| budget.generated(false))
+                .filter(|child| child.used_chars_mask != covered)
+                .max_by_key(|child| {
+                    (
+                        child.used_chars_mask.count_ones(),
+                        Reverse(child.total_letters),
+                    )
+                })
+                .ok_or(SolveError::NotFound)?;
+            path.push(table.words[state.word.unwrap()].clone());
+        }
+        Ok(path.into())
+    }
+}
+
+/// Breadth-first search that only keeps the `width` best states of every layer, ranked with the
+/// same score as [`super::solve`]. Returns the solution with the fewest letters from the first layer that
+/// reaches one.
+#[derive(Debug, Clone, Copy)]
+pub struct Beam {
+    pub width: usize,
+}
+
+impl Default for Beam {
+    fn default() -> Self {
+        Beam { width: 64 }
+    }
+}
+
+impl Solver for Beam {
+    fn name(&self) -> &'static str {
+        "beam"
+    }
+
+    fn exact(&self) -> bool {
+        false
+    }
+
+    fn solve(
+        &self,
+        board: &Board,
+        trie: &Trie,
+        options: &SolveOptions,
+    ) -> Result<Solution, SolveError> {
+        let mut budget = Budget::new(options);
+        let table = WordTable::new(board, trie, options);
+        budget.phase(Phase::Search);
+        let start = State::start();
+        let mut parent = ParentMap::new();
+        let (idx, _) = parent.insert_full(start.key(&table), (start, 0));
+        let mut beam = vec![idx];
+
+        for _ in 0..options.max_words {
+            let mut next_beam = vec![];
+            let mut goal: Option<(usize, usize)> = None;
+            for &parent_state_idx in &beam {
+                let (_, &(parent_state, _)) = parent.get_index(parent_state_idx).unwrap();
+                budget.expand(parent_state.used_chars_mask, parent.len(), beam.len())?;
+                budget.trace(
+                    parent_state.last_word(&table),
+                    parent_state.used_chars_mask,
+                    &parent_state.calculate_score().0,
+                );
+                for child_state in parent_state.get_child_states(&table) {
+                    let Entry::Vacant(entry) = parent.entry(child_state.key(&table)) else {
+                        budget.generated(true);
+                        continue;
+                    };
+                    budget.generated(false);
+                    let child_state_idx = entry.index();
+                    entry.insert((child_state, parent_state_idx));
+                    if child_state.is_goal() {
+                        if goal.is_none_or(|(letters, _)| child_state.total_letters < letters) {
+                            goal = Some((child_state.total_letters, child_state_idx));
+                        }
+                    } else {
+                        next_beam.push((child_state.calculate_score(), child_state_idx));
+                    }
+                }
+            }
+            if let Some((_, idx)) = goal {
+                budget.phase(Phase::Reconstruct);
+                return Ok(extract_path(&parent, &table, idx).into());
+            }
+            next_beam.sort_by(|a, b| b.cmp(a));
+            next_beam.truncate(self.width);
+            beam = next_beam.into_iter().map(|(_, idx)| idx).collect();
+        }
+        Err(SolveError::NotFound)
+    }
+}
+
+/// Every built-in strategy with its default settings
+pub fn all_solvers() -> Vec<Box<dyn Solver>> {
+    vec![
+        Box::new(AStar::default()),
+        Box::new(MinimalAStar),
+        Box::new(BreadthFirst),
+        Box::new(Greedy),
+        Box::new(Beam::default()),
+        Box::new(super::Parallel::default()),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solver::{pareto_front, FewestLetters, FewestRepeats, FewestWords, Parallel};
+    use crate::trie::{read_words, DEFAULT_WORDS};
+    use crate::validate::validate;
+
+    /// panic if `solution` breaks any rule of the game
+    fn check_solution(board: &Board, trie: &Trie, solution: &Solution) {
+        assert!(!solution.words.is_empty());
+        assert_eq!(validate(board, trie, &solution.words), Ok(()));
+    }
+
+    #[test]
+    fn strategies_agree() {
+        let options = SolveOptions {
+            max_words: 6,
+            ..Default::default()
+        };
+        for letters in [
+            "degluysftnwr",
+            "vkspyielurao",
+            "taiconvmhrle",
+            "aiqnbdmeosru",
+        ] {
+            let board = Board::from(letters.chars());
+            let trie = Trie::new_with_board(read_words(DEFAULT_WORDS), &board);
+            let min_words = solve_dp(&board, &trie, &options).unwrap().words.len();
+
+            // a single worker thread takes a different path through the parallel search
+            let single_thread: Box<dyn Solver> = Box::new(Parallel { threads: 1 });
+            for solver in all_solvers().into_iter().chain([single_thread]) {
+                let solution = solver.solve(&board, &trie, &options);
+                if solver.exact() {
+                    assert!(solution.is_ok(), "{}", solver.name());
+                }
+                let Ok(solution) = solution else {
+                    continue;
+                };
+                check_solution(&board, &trie, &solution);
+                assert!(solution.words.len() >= min_words, "{}", solver.name());
+                if solver.exact() {
+                    assert_eq!(solution.words.len(), min_words, "{}", solver.name());
+                    assert_eq!(solution.ruled_out, min_words - 1, "{}", solver.name());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn astar_objectives() {
+        let options = SolveOptions {
+            max_words: 6,
+            ..Default::default()
+        };
+        for letters in ["degluysftnwr", "vkspyielurao", "taiconvmhrle"] {
+            let board = Board::from(letters.chars());
+            let trie = Trie::new_with_board(read_words(DEFAULT_WORDS), &board);
+            let min_words = solve_dp(&board, &trie, &options).unwrap().words.len();
+
+            let words = AStar {
+                scorer: Box::new(FewestWords),
+            };
+            let solution = words.solve(&board, &trie, &options).unwrap();
+            check_solution(&board, &trie, &solution);
+            assert_eq!(solution.words.len(), min_words);
+
+            // fewest letters can't do worse than any other strategy
+            let letters = AStar {
+                scorer: Box::new(FewestLetters),
+            };
+            let solution = letters.solve(&board, &trie, &options).unwrap();
+            check_solution(&board, &trie, &solution);
+            for solver in all_solvers() {
+                if let Ok(other) = solver.solve(&board, &trie, &options) {
+                    assert!(solution.total_letters() <= other.total_letters());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn astar_within_word_limit() {
+        // "adgj", "jbeh" reach the same state as "adgjadgjbeh" with fewer letters, but only the
+        // longer word leaves room to finish within two words
+        let board = Board::from("abc def ghi jkl".chars());
+        let trie = Trie::new_with_board(["adgj", "jbeh", "hkcfil", "adgjadgjbeh"], &board);
+        let options = SolveOptions {
+            max_words: 2,
+            ..Default::default()
+        };
+        // the last point of the front types the fewest letters within the word limit
+        let front = pareto_front(&board, &trie, &options).unwrap();
+        let fewest_letters = front.last().unwrap().total_letters();
+        let scorers: [Box<dyn Scorer>; 2] = [Box::new(FewestLetters), Box::new(FewestRepeats)];
+        for scorer in scorers {
+            let solution = AStar { scorer }.solve(&board, &trie, &options).unwrap();
+            assert_eq!(solution.words, vec!["adgjadgjbeh", "hkcfil"]);
+            assert_eq!(solution.total_letters(), fewest_letters);
+        }
+    }
+
+    #[test]
+    fn greedy_dead_end() {
+        // greedy takes "previously" and then has nothing left to play
+        let board = Board::from("vkspyielurao".chars());
+        let trie = Trie::new_with_board(["previously", "kayo", "oak"], &board);
+        assert_eq!(
+            Greedy.solve(&board, &trie, &SolveOptions::default()),
+            Err(SolveError::NotFound)
+        );
+    }
+
+    #[test]
+    fn constraints_apply_to_all_strategies() {
+        let board = Board::from("degluysftnwr".chars());
+        let trie = Trie::new_with_board(read_words(DEFAULT_WORDS), &board);
+        let options = SolveOptions {
+            start_with: Some("synfuel".into()),
+            must_include: vec!["swung".into()],
+            exclude: ["lewd".to_string()].into(),
+            ..Default::default()
+        };
+        for solver in all_solvers() {
+            let Ok(solution) = solver.solve(&board, &trie, &options) else {
+                assert!(!solver.exact(), "{}", solver.name());
+                continue;
+            };
+            check_solution(&board, &trie, &solution);
+            assert_eq!(solution.words[0], "synfuel", "{}", solver.name());
+            assert!(
+                solution.words.contains(&"swung".into()),
+                "{}",
+                solver.name()
+            );
+            assert!(
+                !solution.words.contains(&"lewd".into()),
+                "{}",
+                solver.name()
+            );
+        }
+
+        let options = SolveOptions {
+            must_include: vec!["flung".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            MinimalAStar.solve(&board, &trie, &options),
+            Err(SolveError::Unsolvable { max_words: 8 })
+        );
+
+        // every letter is covered by the first word, which isn't a goal without the required one
+        let board = Board::from("abc def ghi jkl".chars());
+        let trie =
+            Trie::new_with_board(["gbehkcfilbehkcfil", "ladgjbehkcfi", "ial", "ladj"], &board);
+        let options = SolveOptions {
+            must_include: vec!["ladj".into()],
+            ..Default::default()
+        };
+        for solver in all_solvers().into_iter().filter(|s| s.exact()) {
+            let solution = solver.solve(&board, &trie, &options).unwrap();
+            assert_eq!(
+                solution.words,
+                vec!["gbehkcfilbehkcfil", "ladj"],
+                "{}",
+                solver.name()
+            );
+            assert_eq!(solution.ruled_out, 1, "{}", solver.name());
+        }
+    }
+
+    #[test]
+    fn length_constraints() {
+        let board = Board::from("degluysftnwr".chars());
+        let trie = Trie::new_with_board(read_words(DEFAULT_WORDS), &board);
+        let options = SolveOptions {
+            exact_words: Some(5),
+            min_word_len: Some(4),
+            max_word_len: Some(5),
+            ..Default::default()
+        };
+        for solver in all_solvers() {
+            let Ok(solution) = solver.solve(&board, &trie, &options) else {
+                assert!(!solver.exact(), "{}", solver.name());
+                continue;
+            };
+            check_solution(&board, &trie, &solution);
+            assert_eq!(solution.words.len(), 5, "{}", solver.name());
+            // shorter solutions were never searched
+            assert_eq!(solution.ruled_out, 0, "{}", solver.name());
+            for word in &solution.words {
+                assert!((4..=5).contains(&word.len()), "{}", solver.name());
+            }
+        }
+
+        // DWELT, TURFY, YES, SUNG is the shortest solution
+        for max_letters in [16, 17] {
+            let options = SolveOptions {
+                max_letters: Some(max_letters),
+                ..Default::default()
+            };
+            for solver in all_solvers().into_iter().filter(|s| s.exact()) {
+                let result = solver.solve(&board, &trie, &options);
+                match max_letters {
+                    16 => assert_eq!(
+                        result,
+                        Err(SolveError::Unsolvable { max_words: 8 }),
+                        "{}",
+                        solver.name()
+                    ),
+                    _ => assert_eq!(result.unwrap().total_letters(), 17, "{}", solver.name()),
+                }
+            }
+        }
+    }
+}
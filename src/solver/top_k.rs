@@ -0,0 +1,172 @@
+use super::{Budget, Phase, Solution, SolveError, SolveOptions, State, WordTable};
+use crate::board::Board;
+use crate::trie::Trie;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::str::FromStr;
+
+/// How different the solutions returned by [`solve_top_k`] have to be from each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Diversity {
+    /// Any two distinct solutions are allowed.
+    #[default]
+    Any,
+    /// A solution needs at least this many words that don't appear in any better solution
+    /// returned.
+    MinNewWords(usize),
+    /// No word may appear in more than one solution.
+    Disjoint,
+}
+
+impl Diversity {
+    /// check if `words` is different enough from every solution already accepted
+    fn allows(&self, words: &[usize], accepted: &[Vec<usize>]) -> bool {
+        let new_words = |other: &Vec<usize>| words.iter().filter(|w| !other.contains(w)).count();
+        match *self {
+            Diversity::Any => true,
+            Diversity::MinNewWords(n) => accepted.iter().all(|other| new_words(other) >= n),
+            Diversity::Disjoint => accepted.iter().all(|other| new_words(other) == words.len()),
+        }
+    }
+}
+
+impl FromStr for Diversity {
+    type Err = String;
+
+    /// parse "any", "disjoint" or a minimum number of new words
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "any" => Ok(Diversity::Any),
+            "disjoint" => Ok(Diversity::Disjoint),
+            n => n
+                .parse()
+                .map(Diversity::MinNewWords)
+                .map_err(|_| format!("expected any, disjoint or a number, got {}", n)),
+        }
+    }
+}
+
+/// Find the `k` best solutions, ranked by fewest words and then fewest letters, skipping any
+/// solution that isn't different enough from the better ones according to `diversity`.
+///
+/// The search expands partial solutions ordered by a lower bound on (words, letters) of any
+/// solution extending them, so solutions come out best first. Unlike [`super::solve`] it doesn't
+/// merge paths reaching the same state, as those lead to different solutions.
+pub fn solve_top_k(
+    board: &Board,
+    trie: &Trie,
+    k: usize,
+    diversity: Diversity,
+    options: &SolveOptions,
+) -> Result<Vec<Solution>, SolveError> {
+    let mut budget = Budget::new(options);
+    let table = WordTable::new(board, trie, options);
+    budget.phase(Phase::Search);
+    let max_cover = table.max_cover();
+
+    // every partial solution explored, with the index of the one it extends
+    let mut paths = vec![(State::start(), 0)];
+    let mut queue = BinaryHeap::new();
+    queue.push((Reverse((0, 0)), 0));
+    let mut accepted: Vec<Vec<usize>> = vec![];
+
+    while let Some((_, idx)) = queue.pop() {
+        if accepted.len() >= k {
+            break;
+        }
+        let (state, _) = paths[idx];
+        if state.is_goal() {
+            let mut words = vec![];
+            let mut cursor = idx;
+            while let (State { word: Some(id), .. }, next) = paths[cursor] {
+                words.push(id);
+                cursor = next;
+            }
+            words.reverse();
+            if diversity.allows(&words, &accepted) {
+                accepted.push(words);
+            }
+            continue;
+        }
+        if state.path_len >= options.max_words {
+            continue;
+        }
+        budget.expand(state.used_chars_mask, paths.len(), queue.len())?;
+        for child in state.get_child_states(&table) {
+            let bound = (
+                child.path_len + child.remaining_words_bound(max_cover),
+                child.total_letters + child.partial(max_cover).uncovered(),
+            );
+            if bound.0 > options.max_words {
+                continue;
+            }
+            budget.generated(false);
+            paths.push((child, idx));
+            queue.push((Reverse(bound), paths.len() - 1));
+        }
+    }
+
+    let Some(best) = accepted.first() else {
+        return Err(SolveError::Unsolvable {
+            max_words: options.max_words,
+        });
+    };
+    let ruled_out = options.ruled_out(best.len());
+    budget.phase(Phase::Reconstruct);
+    Ok(accepted
+        .into_iter()
+        .map(|words| Solution {
+            words: words
+                .into_iter()
+                .map(|id| table.words[id].clone())
+                .collect(),
+            ruled_out,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn top_k_diversity() {
+        let board = Board::from("vkspyielurao".chars());
+        let word_list = vec!["previously", "yak", "kayo", "yolk", "kea"];
+        let trie = Trie::new_with_board(word_list, &board);
+        let options = SolveOptions {
+            max_words: 3,
+            ..Default::default()
+        };
+        let words = |diversity| {
+            solve_top_k(&board, &trie, 3, diversity, &options)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.words.join(" "))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            words(Diversity::Any),
+            vec![
+                "previously yak",
+                "previously yolk kea",
+                "previously yolk kayo"
+            ]
+        );
+        assert_eq!(words(Diversity::MinNewWords(1)), words(Diversity::Any));
+        assert_eq!(
+            words(Diversity::MinNewWords(2)),
+            vec!["previously yak", "previously yolk kea"]
+        );
+        assert_eq!(words(Diversity::Disjoint), vec!["previously yak"]);
+    }
+
+    #[test]
+    fn parse_diversity() {
+        assert_eq!("any".parse(), Ok(Diversity::Any));
+        assert_eq!("disjoint".parse(), Ok(Diversity::Disjoint));
+        assert_eq!("2".parse(), Ok(Diversity::MinNewWords(2)));
+        assert!("two".parse::<Diversity>().is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use super::{solve_minimal, Budget, Phase, SolveError, SolveOptions, State, WordTable};
+use crate::board::Board;
+use crate::trie::Trie;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+/// The distinct solutions with the fewest words, found by [`minimal_solutions`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinimalSolutions {
+    /// number of words of every solution
+    pub par: usize,
+    pub solutions: Vec<Vec<String>>,
+    /// set if the search stopped at the limit with more solutions left
+    pub truncated: bool,
+}
+
+impl MinimalSolutions {
+    /// whether exactly one solution uses the fewest words
+    pub fn is_unique(&self) -> bool {
+        self.solutions.len() == 1 && !self.truncated
+    }
+
+    /// Every sequence of junction letters the solutions pass through, with the number of
+    /// solutions passing through it, most common first.
+    pub fn junctions(&self) -> Vec<(Vec<char>, usize)> {
+        let mut counts: HashMap<Vec<char>, usize> = HashMap::new();
+        for words in &self.solutions {
+            *counts.entry(junctions(words)).or_default() += 1;
+        }
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_by(|a, b| (Reverse(a.1), &a.0).cmp(&(Reverse(b.1), &b.0)));
+        counts
+    }
+}
+
+/// The letters shared by consecutive words, where one word ends and the next one starts
+pub fn junctions<S: AsRef<str>>(words: &[S]) -> Vec<char> {
+    words
+        .iter()
+        .take(words.len().saturating_sub(1))
+        .map(|w| w.as_ref().chars().last().unwrap())
+        .collect()
+}
+
+/// Find the distinct solutions using the fewest words, stopping once `limit` are found.
+///
+/// The fewest words are found with [`solve_minimal`], then a depth-first search over the solver
+/// states collects every path reaching the goal in that many words, skipping states that can't
+/// finish in time given the most letters a single word covers.
+pub fn minimal_solutions(
+    board: &Board,
+    trie: &Trie,
+    options: &SolveOptions,
+    limit: Option<usize>,
+) -> Result<MinimalSolutions, SolveError> {
+    let par = solve_minimal(board, trie, options)?.words.len();
+    let mut budget = Budget::new(options);
+    let table = WordTable::new(board, trie, options);
+    budget.phase(Phase::Search);
+
+    let mut search = Search {
+        table: &table,
+        budget: &mut budget,
+        par,
+        max_cover: table.max_cover(),
+        limit: limit.unwrap_or(usize::MAX),
+        path: vec![],
+        solutions: vec![],
+    };
+    let truncated = search.collect(State::start())?.is_break();
+    let solutions = search.solutions;
+    Ok(MinimalSolutions {
+        par,
+        solutions,
+        truncated,
+    })
+}
+
+/// depth first search for [`minimal_solutions`]
+struct Search<'a, 'o> {
+    table: &'a WordTable,
+    budget: &'a mut Budget<'o>,
+    par: usize,
+    max_cover: usize,
+    limit: usize,
+    /// ids of the words played to reach the current state
+    path: Vec<usize>,
+    solutions: Vec<Vec<String>>,
+}
+
+impl Search<'_, '_> {
+    /// Visit every solution extending `state`, breaking once the limit is reached.
+    fn collect(&mut self, state: State) -> Result<ControlFlow<()>, SolveError> {
+        self.budget
+            .expand(state.used_chars_mask, self.path.len(), self.solutions.len())?;
+        for child in state.get_child_states(self.table) {
+            self.budget.generated(false);
+            let id = child.word.unwrap();
+            if child.is_goal() {
+                // no solution is shorter than par, and no path is extended past it
+                debug_assert_eq!(child.path_len, self.par);
+                if self.solutions.len() == self.limit {
+                    return Ok(ControlFlow::Break(()));
+                }
+                let words = self.path.iter().chain([&id]);
+                self.solutions
+                    .push(words.map(|&i| self.table.words[i].clone()).collect());
+            } else if child.path_len + child.remaining_words_bound(self.max_cover) <= self.par {
+                self.path.push(id);
+                let flow = self.collect(child)?;
+                self.path.pop();
+                if flow.is_break() {
+                    return Ok(flow);
+                }
+            }
+        }
+        Ok(ControlFlow::Continue(()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn count_minimal_solutions() {
+        let board = Board::from("vkspyielurao".chars());
+        let trie =
+            Trie::new_with_board(["previously", "yak", "yolk", "kayo", "perilously"], &board);
+        let options = SolveOptions::default();
+        let found = minimal_solutions(&board, &trie, &options, None).unwrap();
+        assert_eq!(found.par, 2);
+        assert_eq!(found.solutions, vec![vec!["previously", "yak"]]);
+        assert!(found.is_unique());
+        assert_eq!(found.junctions(), vec![(vec!['y'], 1)]);
+
+        let trie = Trie::new_with_board(["previously", "yak", "yaka", "yap"], &board);
+        let found = minimal_solutions(&board, &trie, &options, None).unwrap();
+        assert_eq!(found.solutions.len(), 2);
+        assert!(!found.is_unique());
+        assert_eq!(found.junctions(), vec![(vec!['y'], 2)]);
+
+        let found = minimal_solutions(&board, &trie, &options, Some(1)).unwrap();
+        assert_eq!(found.solutions.len(), 1);
+        assert!(found.truncated);
+        assert!(!found.is_unique());
+
+        assert_eq!(junctions(&["sentry", "yes", "self"]), vec!['y', 's']);
+
+        // the first word covers every letter but isn't a goal without the required word
+        let board = Board::from("abc def ghi jkl".chars());
+        let trie =
+            Trie::new_with_board(["gbehkcfilbehkcfil", "ladgjbehkcfi", "ial", "ladj"], &board);
+        let options = SolveOptions {
+            must_include: vec!["ladj".into()],
+            ..Default::default()
+        };
+        let found = minimal_solutions(&board, &trie, &options, None).unwrap();
+        assert_eq!(found.par, 2);
+        assert_eq!(found.solutions, vec![vec!["gbehkcfilbehkcfil", "ladj"]]);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::board::{Board, SIDE_LEN};
+use crate::board::Board;
 use std::cmp::Reverse;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,6 +29,21 @@ impl Node {
     }
 }
 
+/// The bundled 2of12 word list.
+pub const DEFAULT_WORDS: &str = include_str!("2of12.txt");
+
+/// Read the playable words from the contents of a dictionary file. Words shorter than 3 letters
+/// and possessives are skipped.
+pub fn read_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.lines().filter_map(|l| {
+        if l.len() < 3 || l.ends_with("'s") {
+            None
+        } else {
+            Some(l.trim().to_lowercase())
+        }
+    })
+}
+
 // Root is always at 0, "end word" node is always at 1
 const ROOT: usize = 0;
 const WORD_END: usize = 1;
@@ -75,6 +90,10 @@ impl Trie {
         self.items
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.items == 0
+    }
+
     pub fn contains(&self, word: &str) -> bool {
         let mut current_node_index = ROOT;
 
@@ -179,12 +198,20 @@ impl Trie {
         Some(cursor)
     }
 
-    pub fn iter(&self) -> TrieIterator {
+    pub fn iter(&self) -> TrieIterator<'_> {
         let mut root_children = self.nodes[ROOT].children.clone();
         //sort by
         let reverse_depth_sort = |&idx: &usize| Reverse(self.nodes[idx].min_depth);
         root_children.sort_by_key(reverse_depth_sort);
-        let first_node_idx = root_children.pop().unwrap();
+        let first_node_idx = match root_children.pop() {
+            Some(x) => x,
+            None => {
+                return TrieIterator {
+                    trie: self,
+                    stack: vec![],
+                }
+            }
+        };
         let stack_root = (ROOT, root_children);
         let mut first_children = self.nodes[first_node_idx].children.clone();
         first_children.sort_by_key(reverse_depth_sort);
@@ -195,7 +222,7 @@ impl Trie {
         }
     }
 
-    pub fn iter_from_prefix(&self, prefix: &str) -> TrieIterator {
+    pub fn iter_from_prefix(&self, prefix: &str) -> TrieIterator<'_> {
         let mut node_idx = match self.get_node_from_prefix(prefix) {
             Some(x) => x,
             None => {
@@ -225,18 +252,7 @@ fn legal_word<S>(word: S, board: &Board) -> bool
 where
     S: AsRef<str>,
 {
-    let mut prev_char = None;
-    for c in word.as_ref().chars() {
-        if !board.letters.contains(&c) {
-            return false;
-        }
-        match prev_char {
-            Some(p) if board.get_idx(p) / SIDE_LEN == board.get_idx(c) / SIDE_LEN => return false,
-            _ => (),
-        };
-        prev_char = Some(c);
-    }
-    true
+    board.check_letters(word.as_ref()).is_empty()
 }
 
 #[derive(Debug)]
@@ -267,10 +283,7 @@ impl Iterator for TrieIterator<'_> {
             //     println!("{:?}: {:?}", c, kids);
             // }
 
-            let (node_idx, mut children) = match self.stack.pop() {
-                None => return None,
-                Some(x) => x,
-            };
+            let (node_idx, mut children) = self.stack.pop()?;
             let current_child_idx = children.pop().unwrap();
 
             self.stack.push((node_idx, children));
@@ -414,6 +427,13 @@ mod test {
         assert!(iter.next().is_none());
     }
 
+    #[test]
+    fn empty_trie_iter() {
+        let t = Trie::new(Vec::<&str>::new());
+        assert!(t.is_empty());
+        assert!(t.iter().next().is_none());
+    }
+
     #[test]
     fn test_new_with_board() {
         let board = Board::from("abc def ghi jkl".chars());
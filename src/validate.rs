@@ -0,0 +1,156 @@
+use crate::board::{Board, LetterError, BOARD_LEN, FULL_MASK};
+use crate::trie::Trie;
+use std::fmt;
+
+/// A rule of the game broken by a proposed solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// The word isn't in the dictionary.
+    NotInDictionary,
+    /// The word uses a letter that isn't on the board.
+    NotOnBoard(char),
+    /// The word has two consecutive letters on the same side of the board.
+    SameSide(char, char),
+    /// The word doesn't start with the last letter of the word before it.
+    BrokenChain { expected: char },
+    /// The solution leaves these board letters unused.
+    Uncovered(Vec<char>),
+}
+
+/// A rule broken by a proposed solution, and where
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// position and text of the word breaking the rule, `None` for rules about the whole solution
+    pub word: Option<(usize, String)>,
+    pub rule: Rule,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((position, word)) = &self.word {
+            write!(f, "word {} ({}): ", position + 1, word.to_uppercase())?;
+        }
+        let upper = |c: &char| c.to_ascii_uppercase();
+        match &self.rule {
+            Rule::NotInDictionary => write!(f, "not in the dictionary"),
+            Rule::NotOnBoard(c) => write!(f, "{} is not on the board", upper(c)),
+            Rule::SameSide(a, b) => {
+                write!(f, "{} and {} are on the same side", upper(a), upper(b))
+            }
+            Rule::BrokenChain { expected } => {
+                write!(f, "should start with {}", upper(expected))
+            }
+            Rule::Uncovered(letters) => {
+                let letters: Vec<String> = letters.iter().map(|c| upper(c).to_string()).collect();
+                write!(f, "letters not used: {}", letters.join(", "))
+            }
+        }
+    }
+}
+
+impl From<LetterError> for Rule {
+    fn from(error: LetterError) -> Self {
+        match error {
+            LetterError::NotOnBoard(c) => Rule::NotOnBoard(c),
+            LetterError::SameSide(a, b) => Rule::SameSide(a, b),
+        }
+    }
+}
+
+/// Check a proposed solution against every rule of the game, returning each rule broken.
+///
+/// Words are checked against the dictionary only when their letters fit the board, since a trie
+/// built with [`Trie::new_with_board`] holds no other words.
+pub fn validate<S: AsRef<str>>(
+    board: &Board,
+    trie: &Trie,
+    words: &[S],
+) -> Result<(), Vec<Violation>> {
+    let mut violations = vec![];
+    let mut mask = 0;
+    let mut prev_word: Option<&str> = None;
+    for (position, word) in words.iter().enumerate() {
+        let word = word.as_ref();
+        let mut broken: Vec<Rule> = board
+            .check_letters(word)
+            .into_iter()
+            .map(Rule::from)
+            .collect();
+        if broken.is_empty() && !trie.contains(word) {
+            broken.push(Rule::NotInDictionary);
+        }
+        let expected = prev_word.and_then(|p| p.chars().last());
+        if let Some(expected) = expected.filter(|&e| !word.starts_with(e)) {
+            broken.push(Rule::BrokenChain { expected });
+        }
+        violations.extend(broken.into_iter().map(|rule| Violation {
+            word: Some((position, word.to_string())),
+            rule,
+        }));
+        for c in word.chars() {
+            if let Some(i) = board.letters.iter().position(|&l| l == c) {
+                mask |= 1 << i;
+            }
+        }
+        prev_word = Some(word);
+    }
+    if mask != FULL_MASK {
+        let uncovered = (0..BOARD_LEN)
+            .filter(|i| mask & 1 << i == 0)
+            .map(|i| board.letters[i])
+            .collect();
+        violations.push(Violation {
+            word: None,
+            rule: Rule::Uncovered(uncovered),
+        });
+    }
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validate_rules() {
+        let board = Board::from("vkspyielurao".chars());
+        let trie = Trie::new_with_board(["kayo", "previously", "yak", "yolk"], &board);
+        assert_eq!(validate(&board, &trie, &["previously", "yak"]), Ok(()));
+
+        let violations =
+            validate(&board, &trie, &["previously", "kayo", "yaks", "ok", "zoo"]).unwrap_err();
+        let rules: Vec<_> = violations
+            .iter()
+            .map(|v| (v.word.as_ref().map(|(p, _)| *p), v.rule.clone()))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                (Some(1), Rule::BrokenChain { expected: 'y' }),
+                (Some(2), Rule::SameSide('k', 's')),
+                (Some(2), Rule::BrokenChain { expected: 'o' }),
+                (Some(3), Rule::NotInDictionary),
+                (Some(3), Rule::BrokenChain { expected: 's' }),
+                (Some(4), Rule::NotOnBoard('z')),
+                (Some(4), Rule::SameSide('o', 'o')),
+                (Some(4), Rule::BrokenChain { expected: 'k' }),
+            ]
+        );
+
+        assert_eq!(
+            validate(&board, &trie, &["yolk"]),
+            Err(vec![Violation {
+                word: None,
+                rule: Rule::Uncovered(vec!['v', 's', 'p', 'i', 'e', 'u', 'r', 'a']),
+            }])
+        );
+        assert_eq!(
+            violations[1].to_string(),
+            "word 3 (YAKS): K and S are on the same side"
+        );
+    }
+}